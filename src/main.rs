@@ -1,31 +1,180 @@
-use std::sync::{Arc, Mutex as SyncMutex};
-use anyhow::{Result as AnyResult};
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::{anyhow, bail, Result as AnyResult};
 use futures::future::join_all;
-use crate::primary::crypto::srp::Srp;
-use crate::primary::server::{LoginServer, WorldServer};
-use crate::primary::traits::server::{RunOptions, Server};
+use tokio::signal;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+use tine::primary::accounts::FileAccountRepository;
+use tine::primary::characters::InMemoryCharacterRepository;
+use tine::primary::capture::{CaptureReader, CaptureWriter, Direction, ServerKind};
+use tine::primary::config::{parse_args, Config};
+use tine::primary::logging;
+use tine::primary::server::{LoginServer, RealmStatus, Sessions, Shutdown, WorldServer};
+use tine::primary::traits::account_repository::AccountRepository;
+use tine::primary::traits::server::{RunOptions, Server};
 
-mod primary;
+const USAGE: &str = "Usage: tine [OPTIONS]
+
+Options:
+  --config <PATH>                 config file (default: tine.json)
+  --login-host <HOST>             login server bind address
+  --login-port <PORT>             login server port
+  --world-host <HOST>             world server bind address
+  --world-port <PORT>             world server port of the first realm
+  --realm-address <HOST[:PORT]>   world server address sent in the realm list
+  --player-limit <COUNT>          accounts in the world of every realm, 0 means no limit
+  --accounts <PATH>               accounts file
+  --log-level <FILTER>            error, warn, info, debug or tracing filter (\"info,packets=debug\")
+  --log-file <PATH>               write logs to the file instead of stdout
+  --capture <PATH>                record decrypted packets of all connections into the file
+  --dump <OPCODE>                 log hex dumps of the opcode packets, can be repeated, \"*\" dumps all
+  --drain-timeout <SECONDS>       time for connections to finish after SIGINT or SIGTERM
+  --read-capture <PATH>           print packets recorded with --capture as json and exit
+  --create-account <NAME:PASSWORD>
+                                  add account to the accounts file and exit
+  --help                          print this message
+
+Each option except --config, --read-capture and --create-account can be set with TINE_* env variable
+named after the flag (TINE_LOGIN_PORT, TINE_ACCOUNTS, TINE_DUMP with comma separated opcodes etc),
+command line flags take precedence over env.";
 
 #[tokio::main]
 async fn main() -> AnyResult<()> {
-    let options = Arc::new(RunOptions { srp: Arc::new(SyncMutex::new(Srp::new())) });
-
-    let run_login_server = || {
-        let options = options.clone();
-        tokio::spawn(async move {
-            LoginServer::new().run(options).await
-        })
-    };
-
-    let run_world_server = || {
-        let options = options.clone();
-        tokio::spawn(async move {
-            WorldServer::new().run(options).await
-        })
-    };
-
-    join_all(vec![run_login_server(), run_world_server()]).await;
+    let args = parse_args(env::args().skip(1), USAGE)?;
+    if args.iter().any(|(flag, _)| flag == "help") {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    let config_path = args.iter().find(|(flag, _)| flag == "config").map(|(_, value)| value.as_str());
+    let mut config = Config::load(config_path)?;
+    config.apply_env()?;
+    apply_args(&mut config, &args)?;
+    config.validate()?;
+    logging::init(&config)?;
+
+    if let Some((_, path)) = args.iter().find(|(flag, _)| flag == "read-capture") {
+        return print_capture(path);
+    }
+
+    let accounts = Arc::new(FileAccountRepository::open(&config.accounts_path)?);
+    if let Some((_, value)) = args.iter().find(|(flag, _)| flag == "create-account") {
+        let (name, password) = value.split_once(':')
+            .ok_or_else(|| anyhow!("Account should be passed as NAME:PASSWORD"))?;
+        let account = accounts.create(name, password)?;
+        println!("Account {} is created", account.name);
+        return Ok(());
+    }
+
+    let options = Arc::new(RunOptions {
+        accounts,
+        characters: Arc::new(InMemoryCharacterRepository::new()),
+        sessions: Arc::new(Sessions::new(Duration::from_secs(config.world.session_ttl))),
+        realm_status: RealmStatus::new(),
+        shutdown: Shutdown::new(),
+        capture: config.capture_file.as_deref().map(CaptureWriter::create).transpose()?,
+        config,
+    });
+
+    let mut servers = vec![run_server(LoginServer::new(), &options)];
+    if options.config.features.world_server {
+        for realm in &options.config.realms {
+            servers.push(run_server(WorldServer::new(realm.clone()), &options));
+        }
+    }
+
+    tokio::spawn(shutdown_on_signal(Arc::clone(&options)));
+    for result in join_all(servers).await {
+        match result {
+            Ok(Err(err)) => error!("Server is stopped with error: {}", err),
+            Err(err) => error!("Server task failed: {}", err),
+            Ok(Ok(())) => {},
+        }
+    }
+
+    Ok(())
+}
+
+async fn shutdown_on_signal(options: Arc<RunOptions>) -> AnyResult<()> {
+    #[cfg(unix)]
+    {
+        let mut terminate = signal::unix::signal(signal::unix::SignalKind::terminate())?;
+        tokio::select! {
+            result = signal::ctrl_c() => result?,
+            _ = terminate.recv() => {},
+        }
+    }
+    #[cfg(not(unix))]
+    signal::ctrl_c().await?;
+
+    info!("Shutting down, connections have {} sec to finish", options.config.drain_timeout);
+    options.shutdown.trigger();
+
+    Ok(())
+}
+
+fn print_capture(path: &str) -> AnyResult<()> {
+    for record in CaptureReader::open(path)? {
+        let record = record?;
+        let opcode_name = match record.server {
+            ServerKind::Login => LoginServer::opcode_name(record.opcode),
+            ServerKind::World => WorldServer::opcode_name(record.opcode),
+        };
+        let direction = match record.direction {
+            Direction::Inbound => "<-",
+            Direction::Outbound => "->",
+        };
+
+        println!(
+            "{} #{} :{} {} {} ({} bytes)",
+            record.timestamp,
+            record.connection_id,
+            record.port,
+            direction,
+            opcode_name,
+            record.body.len(),
+        );
+        if let Some(json_details) = &record.json_details {
+            println!("{}", json_details);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_server<S: Server>(server: S, options: &Arc<RunOptions>) -> JoinHandle<AnyResult<()>> {
+    let options = Arc::clone(options);
+    tokio::spawn(async move {
+        Arc::new(server).run(options).await
+    })
+}
+
+fn apply_args(config: &mut Config, args: &[(String, String)]) -> AnyResult<()> {
+    // repeated --dump flags replace the opcodes from the config file and env
+    if args.iter().any(|(flag, _)| flag == "dump") {
+        config.packet_dumps.clear();
+    }
+
+    for (flag, value) in args {
+        match flag.as_str() {
+            "login-host" => config.login.host = value.clone(),
+            "login-port" => config.login.port = value.parse()?,
+            "world-host" => config.world.host = value.clone(),
+            "world-port" => config.world.port = value.parse()?,
+            "realm-address" => config.world.realm_address = Some(value.clone()),
+            "player-limit" => config.world.player_limit = value.parse()?,
+            "accounts" => config.accounts_path = value.clone(),
+            "log-level" => config.log_level = value.clone(),
+            "log-file" => config.log_file = Some(value.clone()),
+            "dump" => config.packet_dumps.push(value.clone()),
+            "capture" => config.capture_file = Some(value.clone()),
+            "drain-timeout" => config.drain_timeout = value.parse()?,
+            "config" | "create-account" | "read-capture" => {},
+            _ => bail!("Unknown option: --{}\n\n{}", flag, USAGE),
+        }
+    }
 
     Ok(())
 }
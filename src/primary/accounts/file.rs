@@ -0,0 +1,98 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Result as AnyResult};
+use serde::{Deserialize, Serialize};
+
+use crate::primary::accounts::InMemoryAccountRepository;
+use crate::primary::traits::account_repository::AccountRepository;
+use crate::primary::types::Account;
+
+// accounts added to the file by hand can be declared with plain password,
+// such entries are replaced with salt and verifier on the next save
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Entry {
+    Stored(Account),
+    Plain { name: String, password: String },
+}
+
+#[derive(Debug)]
+pub struct FileAccountRepository {
+    path: PathBuf,
+    accounts: InMemoryAccountRepository,
+}
+
+impl FileAccountRepository {
+    pub fn open<P: AsRef<Path>>(path: P) -> AnyResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        let accounts = InMemoryAccountRepository::new();
+
+        let mut has_plain_entries = false;
+        if path.exists() {
+            let entries: Vec<Entry> = serde_json::from_str(&fs::read_to_string(&path)?)?;
+            for entry in entries {
+                match entry {
+                    Entry::Stored(account) => accounts.insert(account),
+                    Entry::Plain { name, password } => {
+                        has_plain_entries = true;
+                        accounts.insert(Account::new(&name, &password));
+                    },
+                }
+            }
+        }
+
+        let repository = Self { path, accounts };
+        if has_plain_entries {
+            repository.save()?;
+        }
+
+        Ok(repository)
+    }
+
+    fn save(&self) -> AnyResult<()> {
+        let entries = self.accounts.all().into_iter().map(Entry::Stored).collect::<Vec<_>>();
+        fs::write(&self.path, serde_json::to_string_pretty(&entries)?)?;
+
+        Ok(())
+    }
+}
+
+impl AccountRepository for FileAccountRepository {
+    fn get(&self, name: &str) -> Option<Account> {
+        self.accounts.get(name)
+    }
+
+    fn create(&self, name: &str, password: &str) -> AnyResult<Account> {
+        let account = self.accounts.create(name, password)?;
+        self.save()?;
+
+        Ok(account)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::primary::accounts::FileAccountRepository;
+    use crate::primary::traits::account_repository::AccountRepository;
+
+    #[test]
+    fn test_plain_entries_are_replaced_with_verifier() {
+        let path = std::env::temp_dir().join(format!("tine_accounts_{}.json", rand::random::<u32>()));
+        fs::write(&path, r#"[{ "name": "test", "password": "secret" }]"#).unwrap();
+
+        let repository = FileAccountRepository::open(&path).unwrap();
+        let account = repository.get("Test").unwrap();
+        assert_eq!(account.name, "TEST");
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("secret"));
+
+        let reopened = FileAccountRepository::open(&path).unwrap();
+        assert_eq!(reopened.get("TEST").unwrap().verifier, account.verifier);
+        assert!(reopened.create("test", "other").is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex as SyncMutex};
+use anyhow::{bail, Result as AnyResult};
+
+use crate::primary::traits::account_repository::AccountRepository;
+use crate::primary::types::Account;
+
+#[derive(Debug, Default)]
+pub struct InMemoryAccountRepository {
+    accounts: SyncMutex<BTreeMap<String, Account>>,
+}
+
+impl InMemoryAccountRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, account: Account) {
+        self.accounts.lock().unwrap().insert(account.name.clone(), account);
+    }
+
+    pub fn all(&self) -> Vec<Account> {
+        self.accounts.lock().unwrap().values().cloned().collect()
+    }
+}
+
+impl AccountRepository for InMemoryAccountRepository {
+    fn get(&self, name: &str) -> Option<Account> {
+        self.accounts.lock().unwrap().get(&name.to_uppercase()).cloned()
+    }
+
+    fn create(&self, name: &str, password: &str) -> AnyResult<Account> {
+        if self.get(name).is_some() {
+            bail!("Account {} already exists", name.to_uppercase());
+        }
+
+        let account = Account::new(name, password);
+        self.insert(account.clone());
+
+        Ok(account)
+    }
+}
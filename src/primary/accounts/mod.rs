@@ -0,0 +1,5 @@
+mod file;
+mod in_memory;
+
+pub use file::FileAccountRepository;
+pub use in_memory::InMemoryAccountRepository;
@@ -0,0 +1,239 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, ErrorKind, Read, Write};
+use std::sync::{Mutex as SyncMutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use anyhow::{bail, Context, Result as AnyResult};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+
+// file starts with magic and format version, records follow without any separators:
+// timestamp u64 (ms since unix epoch), connection id u64, server port u16, server kind u8,
+// direction u8, opcode u16, body size u32, body, json details size u32, json details
+const MAGIC: &[u8; 8] = b"TINECAP\0";
+const VERSION: u16 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerKind {
+    Login,
+    World,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    // from client to server
+    Inbound,
+    Outbound,
+}
+
+// decrypted packet without header
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureRecord {
+    pub timestamp: u64,
+    pub connection_id: u64,
+    // port of the server which handled the connection
+    pub port: u16,
+    pub server: ServerKind,
+    pub direction: Direction,
+    pub opcode: u16,
+    pub body: Vec<u8>,
+    // packet struct rendered as json, missing when the packet was not parsed
+    pub json_details: Option<String>,
+}
+
+impl CaptureRecord {
+    pub fn new(
+        connection_id: u64,
+        port: u16,
+        server: ServerKind,
+        direction: Direction,
+        opcode: u16,
+        body: &[u8],
+        json_details: Option<String>,
+    ) -> Self {
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default();
+
+        Self {
+            timestamp,
+            connection_id,
+            port,
+            server,
+            direction,
+            opcode,
+            body: body.to_vec(),
+            json_details,
+        }
+    }
+
+    fn write_into<W: Write>(&self, writer: &mut W) -> AnyResult<()> {
+        writer.write_u64::<LittleEndian>(self.timestamp)?;
+        writer.write_u64::<LittleEndian>(self.connection_id)?;
+        writer.write_u16::<LittleEndian>(self.port)?;
+        writer.write_u8(match self.server {
+            ServerKind::Login => 0,
+            ServerKind::World => 1,
+        })?;
+        writer.write_u8(match self.direction {
+            Direction::Inbound => 0,
+            Direction::Outbound => 1,
+        })?;
+        writer.write_u16::<LittleEndian>(self.opcode)?;
+        writer.write_u32::<LittleEndian>(self.body.len() as u32)?;
+        writer.write_all(&self.body)?;
+
+        let json_details = self.json_details.as_deref().unwrap_or_default();
+        writer.write_u32::<LittleEndian>(json_details.len() as u32)?;
+        writer.write_all(json_details.as_bytes())?;
+
+        Ok(())
+    }
+
+    // returns None when the reader is at the end of the file
+    fn read_from<R: Read>(reader: &mut R) -> AnyResult<Option<Self>> {
+        let timestamp = match reader.read_u64::<LittleEndian>() {
+            Ok(timestamp) => timestamp,
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        let connection_id = reader.read_u64::<LittleEndian>()?;
+        let port = reader.read_u16::<LittleEndian>()?;
+        let server = match reader.read_u8()? {
+            0 => ServerKind::Login,
+            1 => ServerKind::World,
+            server => bail!("Unknown server kind: {}", server),
+        };
+        let direction = match reader.read_u8()? {
+            0 => Direction::Inbound,
+            1 => Direction::Outbound,
+            direction => bail!("Unknown packet direction: {}", direction),
+        };
+        let opcode = reader.read_u16::<LittleEndian>()?;
+        let size = reader.read_u32::<LittleEndian>()?;
+
+        let mut body = vec![0; size as usize];
+        reader.read_exact(&mut body).context("Capture record is truncated")?;
+
+        let size = reader.read_u32::<LittleEndian>().context("Capture record is truncated")?;
+        let mut json_details = vec![0; size as usize];
+        reader.read_exact(&mut json_details).context("Capture record is truncated")?;
+        let json_details = match size {
+            0 => None,
+            _ => Some(String::from_utf8(json_details)?),
+        };
+
+        Ok(Some(Self { timestamp, connection_id, port, server, direction, opcode, body, json_details }))
+    }
+}
+
+// shared by all servers and connections, records are flushed immediately
+#[derive(Debug)]
+pub struct CaptureWriter {
+    writer: SyncMutex<BufWriter<File>>,
+}
+
+impl CaptureWriter {
+    pub fn create(path: &str) -> AnyResult<Self> {
+        let file = File::create(path).with_context(|| format!("Cannot create capture file {}", path))?;
+
+        let mut writer = BufWriter::new(file);
+        writer.write_all(MAGIC)?;
+        writer.write_u16::<LittleEndian>(VERSION)?;
+        writer.flush()?;
+
+        Ok(Self { writer: SyncMutex::new(writer) })
+    }
+
+    pub fn write(&self, record: &CaptureRecord) -> AnyResult<()> {
+        let mut writer = self.writer.lock().unwrap();
+        record.write_into(&mut *writer)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+pub struct CaptureReader<R: Read> {
+    reader: R,
+}
+
+impl CaptureReader<BufReader<File>> {
+    pub fn open(path: &str) -> AnyResult<Self> {
+        let file = File::open(path).with_context(|| format!("Cannot open capture file {}", path))?;
+
+        Self::new(BufReader::new(file))
+    }
+}
+
+impl<R: Read> CaptureReader<R> {
+    pub fn new(mut reader: R) -> AnyResult<Self> {
+        let mut magic = [0; MAGIC.len()];
+        reader.read_exact(&mut magic).context("Capture header is truncated")?;
+        if &magic != MAGIC {
+            bail!("Not a capture file");
+        }
+
+        let version = reader.read_u16::<LittleEndian>()?;
+        if version != VERSION {
+            bail!("Unsupported capture version: {}", version);
+        }
+
+        Ok(Self { reader })
+    }
+}
+
+impl<R: Read> Iterator for CaptureReader<R> {
+    type Item = AnyResult<CaptureRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        CaptureRecord::read_from(&mut self.reader).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::primary::capture::{CaptureReader, CaptureRecord, CaptureWriter, Direction, ServerKind};
+
+    #[test]
+    fn test_capture_round_trip() {
+        let path = std::env::temp_dir().join(format!("tine-capture-{}.bin", rand::random::<u64>()));
+        let path = path.to_str().unwrap();
+
+        let records = vec![
+            CaptureRecord::new(1, 3724, ServerKind::Login, Direction::Inbound, 16, &[0, 0, 0, 0], None),
+            CaptureRecord::new(
+                2, 8999, ServerKind::World, Direction::Outbound, 494, &[12, 0, 0, 0], Some("{\"code\": 12}".to_string()),
+            ),
+        ];
+
+        let writer = CaptureWriter::create(path).unwrap();
+        for record in &records {
+            writer.write(record).unwrap();
+        }
+
+        let read = CaptureReader::open(path).unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(read, records);
+    }
+
+    #[test]
+    fn test_truncated_record() {
+        let mut data = b"TINECAP\0".to_vec();
+        data.extend(1u16.to_le_bytes());
+        data.extend([0; 10]);
+
+        let mut reader = CaptureReader::new(data.as_slice()).unwrap();
+        assert!(reader.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let mut data = b"TINECAP\0".to_vec();
+        data.extend(2u16.to_le_bytes());
+
+        assert!(CaptureReader::new(data.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use std::sync::{Mutex as SyncMutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::primary::traits::character_repository::{CharacterError, CharacterRepository};
+use crate::primary::types::Character;
+
+#[derive(Debug, Default)]
+pub struct InMemoryCharacterRepository {
+    characters: SyncMutex<Vec<Character>>,
+    // the last assigned guid, guids of deleted characters are never reused
+    last_guid: AtomicU64,
+}
+
+impl InMemoryCharacterRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, character: Character) {
+        self.last_guid.fetch_max(character.guid, Ordering::SeqCst);
+        self.characters.lock().unwrap().push(character);
+    }
+
+    // names are unique per realm regardless of the case, other characters cannot take them
+    fn name_taken(characters: &[Character], character: &Character) -> bool {
+        characters.iter().any(|stored| {
+            stored.guid != character.guid
+                && stored.realm_id == character.realm_id
+                && stored.name.eq_ignore_ascii_case(&character.name)
+        })
+    }
+}
+
+impl CharacterRepository for InMemoryCharacterRepository {
+    fn count(&self, account: &str, realm_id: u8) -> usize {
+        self.list(account, realm_id).len()
+    }
+
+    fn list(&self, account: &str, realm_id: u8) -> Vec<Character> {
+        let account = account.to_uppercase();
+
+        self.characters.lock().unwrap()
+            .iter()
+            .filter(|character| character.account == account && character.realm_id == realm_id)
+            .cloned()
+            .collect()
+    }
+
+    fn get(&self, guid: u64) -> Option<Character> {
+        self.characters.lock().unwrap()
+            .iter()
+            .find(|character| character.guid == guid)
+            .cloned()
+    }
+
+    fn find_by_name(&self, name: &str, realm_id: u8) -> Option<Character> {
+        self.characters.lock().unwrap()
+            .iter()
+            .find(|character| character.realm_id == realm_id && character.name.eq_ignore_ascii_case(name))
+            .cloned()
+    }
+
+    fn create(&self, mut character: Character) -> Result<Character, CharacterError> {
+        let mut characters = self.characters.lock().unwrap();
+        if Self::name_taken(&characters, &character) {
+            return Err(CharacterError::NameInUse);
+        }
+
+        character.guid = self.last_guid.fetch_add(1, Ordering::SeqCst) + 1;
+        characters.push(character.clone());
+
+        Ok(character)
+    }
+
+    fn update(&self, character: Character) -> Result<(), CharacterError> {
+        let mut characters = self.characters.lock().unwrap();
+        if Self::name_taken(&characters, &character) {
+            return Err(CharacterError::NameInUse);
+        }
+
+        let stored = characters.iter_mut()
+            .find(|stored| stored.guid == character.guid)
+            .ok_or(CharacterError::NotFound)?;
+        *stored = character;
+
+        Ok(())
+    }
+
+    fn delete(&self, guid: u64) -> Option<Character> {
+        let mut characters = self.characters.lock().unwrap();
+        let index = characters.iter().position(|character| character.guid == guid)?;
+
+        Some(characters.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primary::characters::InMemoryCharacterRepository;
+    use crate::primary::traits::character_repository::{CharacterError, CharacterRepository};
+    use crate::primary::types::Character;
+
+    fn character(name: &str) -> Character {
+        Character { account: "TEST".to_string(), realm_id: 1, name: name.to_string(), ..Character::default() }
+    }
+
+    #[test]
+    fn test_create_and_delete() {
+        let characters = InMemoryCharacterRepository::new();
+        let first = characters.create(character("First")).unwrap();
+        let second = characters.create(character("Second")).unwrap();
+
+        assert_eq!((first.guid, second.guid), (1, 2));
+        assert_eq!(characters.list("test", 1), vec![first.clone(), second.clone()]);
+        assert_eq!(characters.find_by_name("SECOND", 1), Some(second.clone()));
+        assert_eq!(characters.find_by_name("Second", 2), None);
+
+        assert_eq!(characters.update(Character { at_login: 1, ..second.clone() }), Ok(()));
+        assert_eq!(characters.get(second.guid).map(|character| character.at_login), Some(1));
+        assert_eq!(characters.update(character("Third")), Err(CharacterError::NotFound));
+
+        assert_eq!(characters.create(character("FIRST")), Err(CharacterError::NameInUse));
+        assert_eq!(characters.update(Character { name: "first".to_string(), ..second.clone() }), Err(CharacterError::NameInUse));
+
+        assert_eq!(characters.delete(first.guid), Some(first));
+        assert_eq!(characters.get(1), None);
+        assert_eq!(characters.count("TEST", 1), 1);
+
+        // guid of the deleted newest character is not given to the next one
+        characters.delete(second.guid);
+        assert_eq!(characters.create(character("Third")).unwrap().guid, 3);
+    }
+}
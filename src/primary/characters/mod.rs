@@ -0,0 +1,3 @@
+mod in_memory;
+
+pub use in_memory::InMemoryCharacterRepository;
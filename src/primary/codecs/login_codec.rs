@@ -0,0 +1,92 @@
+use std::sync::{Arc, Mutex as SyncMutex};
+use anyhow::{bail, Result as AnyResult};
+
+use crate::primary::server::Opcode;
+use crate::primary::traits::packet_codec::PacketCodec;
+use crate::primary::types::{IncomingPacket, Session};
+
+// opcode + error + 2 bytes size of the rest of the packet
+const CHALLENGE_HEADER_SIZE: usize = 4;
+// login packets below have no size field, so their size is fixed
+const LOGIN_PROOF_SIZE: usize = 75;
+const RECONNECT_PROOF_SIZE: usize = 58;
+const REALM_LIST_SIZE: usize = 5;
+
+#[derive(Debug, Default)]
+pub struct LoginCodec;
+
+impl PacketCodec for LoginCodec {
+    fn decode(
+        &mut self,
+        buffer: &mut Vec<u8>,
+        _: &Arc<SyncMutex<Session>>,
+    ) -> AnyResult<Option<IncomingPacket>> {
+        let Some(&opcode) = buffer.first() else {
+            return Ok(None);
+        };
+
+        let size = match opcode {
+            Opcode::LOGIN_CHALLENGE | Opcode::RECONNECT_CHALLENGE => {
+                if buffer.len() < CHALLENGE_HEADER_SIZE {
+                    return Ok(None);
+                }
+
+                CHALLENGE_HEADER_SIZE + u16::from_le_bytes([buffer[2], buffer[3]]) as usize
+            },
+            Opcode::LOGIN_PROOF => LOGIN_PROOF_SIZE,
+            Opcode::RECONNECT_PROOF => RECONNECT_PROOF_SIZE,
+            Opcode::REALM_LIST => REALM_LIST_SIZE,
+            _ => bail!("Unknown login opcode: {}", opcode),
+        };
+
+        if buffer.len() < size {
+            return Ok(None);
+        }
+
+        let packet = buffer.drain(..size).collect::<Vec<u8>>();
+
+        Ok(Some(IncomingPacket {
+            opcode: opcode as u16,
+            body: packet[1..].to_vec(),
+        }))
+    }
+
+    fn encode(&mut self, packet: Vec<u8>, _: &Arc<SyncMutex<Session>>) -> Vec<u8> {
+        packet
+    }
+
+    fn outgoing_parts<'a>(&self, packet: &'a [u8]) -> (u16, &'a [u8]) {
+        (packet[0] as u16, &packet[1..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex as SyncMutex};
+
+    use crate::primary::codecs::LoginCodec;
+    use crate::primary::server::Opcode;
+    use crate::primary::traits::packet_codec::PacketCodec;
+    use crate::primary::types::Session;
+
+    #[test]
+    fn test_coalesced_and_fragmented_packets() {
+        let session = Arc::new(SyncMutex::new(Session::new()));
+        let mut codec = LoginCodec;
+
+        let challenge = [vec![Opcode::LOGIN_CHALLENGE, 0, 3, 0], vec![1, 2, 3]].concat();
+        let realm_list = [Opcode::REALM_LIST, 0, 0, 0, 0];
+
+        let mut buffer = [challenge, realm_list[..2].to_vec()].concat();
+
+        let packet = codec.decode(&mut buffer, &session).unwrap().unwrap();
+        assert_eq!(packet.opcode, Opcode::LOGIN_CHALLENGE as u16);
+        assert_eq!(packet.body, vec![0, 3, 0, 1, 2, 3]);
+        assert!(codec.decode(&mut buffer, &session).unwrap().is_none());
+
+        buffer.extend(&realm_list[2..]);
+        let packet = codec.decode(&mut buffer, &session).unwrap().unwrap();
+        assert_eq!(packet.opcode, Opcode::REALM_LIST as u16);
+        assert!(buffer.is_empty());
+    }
+}
@@ -0,0 +1,5 @@
+mod login_codec;
+mod world_codec;
+
+pub use login_codec::LoginCodec;
+pub use world_codec::WorldCodec;
@@ -0,0 +1,119 @@
+use std::sync::{Arc, Mutex as SyncMutex};
+use anyhow::{bail, Result as AnyResult};
+
+use crate::primary::traits::packet_codec::PacketCodec;
+use crate::primary::types::{IncomingPacket, Session};
+
+// client header: 2 bytes size (big-endian) + 4 bytes opcode
+const CLIENT_HEADER_SIZE: usize = 6;
+const CLIENT_OPCODE_SIZE: usize = 4;
+const SERVER_OPCODE_SIZE: usize = 2;
+// bigger server packets use 3 bytes size with the highest bit set
+const MAX_SMALL_PACKET_SIZE: usize = 0x7FFF;
+
+#[derive(Debug, Default)]
+pub struct WorldCodec {
+    // header is decrypted only once, so it should be kept until the body is received
+    header: Option<(usize, u32)>,
+}
+
+impl PacketCodec for WorldCodec {
+    fn decode(
+        &mut self,
+        buffer: &mut Vec<u8>,
+        session: &Arc<SyncMutex<Session>>,
+    ) -> AnyResult<Option<IncomingPacket>> {
+        if self.header.is_none() {
+            if buffer.len() < CLIENT_HEADER_SIZE {
+                return Ok(None);
+            }
+
+            let mut header = buffer.drain(..CLIENT_HEADER_SIZE).collect::<Vec<u8>>();
+            if let Some(header_crypt) = session.lock().unwrap().header_crypt.as_mut() {
+                header_crypt.decrypt(&mut header);
+            }
+
+            let size = u16::from_be_bytes([header[0], header[1]]) as usize;
+            if size < CLIENT_OPCODE_SIZE {
+                bail!("Invalid packet size: {}", size);
+            }
+
+            let opcode = u32::from_le_bytes([header[2], header[3], header[4], header[5]]);
+            self.header = Some((size - CLIENT_OPCODE_SIZE, opcode));
+        }
+
+        let (body_size, opcode) = self.header.unwrap();
+        if buffer.len() < body_size {
+            return Ok(None);
+        }
+
+        self.header = None;
+
+        Ok(Some(IncomingPacket {
+            opcode: opcode as u16,
+            body: buffer.drain(..body_size).collect(),
+        }))
+    }
+
+    // packets are built with client header (2 bytes size + 4 bytes opcode),
+    // server header is 2 bytes size (3 bytes for large packets) + 2 bytes opcode
+    fn encode(&mut self, packet: Vec<u8>, session: &Arc<SyncMutex<Session>>) -> Vec<u8> {
+        let opcode = [packet[2], packet[3]];
+        let body = &packet[CLIENT_HEADER_SIZE..];
+        let size = body.len() + SERVER_OPCODE_SIZE;
+
+        let mut header = match size > MAX_SMALL_PACKET_SIZE {
+            true => vec![0x80 | (size >> 16) as u8, (size >> 8) as u8, size as u8],
+            false => (size as u16).to_be_bytes().to_vec(),
+        };
+        header.extend(opcode);
+
+        if let Some(header_crypt) = session.lock().unwrap().header_crypt.as_mut() {
+            header_crypt.encrypt(&mut header);
+        }
+
+        [header, body.to_vec()].concat()
+    }
+
+    fn outgoing_parts<'a>(&self, packet: &'a [u8]) -> (u16, &'a [u8]) {
+        (u16::from_le_bytes([packet[2], packet[3]]), &packet[CLIENT_HEADER_SIZE..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex as SyncMutex};
+
+    use crate::primary::codecs::WorldCodec;
+    use crate::primary::traits::packet_codec::PacketCodec;
+    use crate::primary::types::Session;
+
+    #[test]
+    fn test_fragmented_packet() {
+        let session = Arc::new(SyncMutex::new(Session::new()));
+        let mut codec = WorldCodec::default();
+
+        let mut buffer = vec![0, 7, 0xDC, 0x01, 0, 0, 1];
+        assert!(codec.decode(&mut buffer, &session).unwrap().is_none());
+
+        buffer.extend([2, 3, 0, 4]);
+        let packet = codec.decode(&mut buffer, &session).unwrap().unwrap();
+        assert_eq!(packet.opcode, 0x01DC);
+        assert_eq!(packet.body, vec![1, 2, 3]);
+        assert_eq!(buffer, vec![0, 4]);
+    }
+
+    #[test]
+    fn test_large_packet_header() {
+        let session = Arc::new(SyncMutex::new(Session::new()));
+        let mut codec = WorldCodec::default();
+
+        let small = codec.encode([vec![0, 5, 0xA9, 0x00, 0, 0], vec![1]].concat(), &session);
+        assert_eq!(small, vec![0, 3, 0xA9, 0x00, 1]);
+
+        let body = vec![0u8; 0x8000];
+        let large = codec.encode([vec![0, 0, 0xA9, 0x00, 0, 0], body].concat(), &session);
+        assert_eq!(large[..5], [0x80, 0x80, 0x02, 0xA9, 0x00]);
+        assert_eq!(large.len(), 5 + 0x8000);
+    }
+}
@@ -0,0 +1,343 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use anyhow::{anyhow, bail, Context, Result as AnyResult};
+use serde::{Deserialize, Serialize};
+
+use crate::primary::types::fields::realms::RealmBuild;
+
+pub const DEFAULT_CONFIG_PATH: &str = "tine.json";
+const ENV_PREFIX: &str = "TINE_";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Config {
+    pub login: LoginConfig,
+    pub world: WorldConfig,
+    pub accounts_path: String,
+    // level or tracing filter directives, for example "info,packets=debug"
+    pub log_level: String,
+    // logs are written to stdout when omitted
+    pub log_file: Option<String>,
+    // opcode names which packets are logged as hex dumps, "*" dumps all packets
+    pub packet_dumps: Vec<String>,
+    // file to record decrypted packets of all connections, see CaptureReader
+    pub capture_file: Option<String>,
+    // seconds connections can finish their work after the shutdown is triggered
+    pub drain_timeout: u64,
+    pub features: Features,
+    pub realms: Vec<RealmConfig>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct LoginConfig {
+    pub host: String,
+    pub port: u16,
+    // client builds and platforms accepted on LOGIN_CHALLENGE
+    pub allowed_builds: Vec<u16>,
+    pub allowed_platforms: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct WorldConfig {
+    pub host: String,
+    // port of the first realm, realms without explicit port take the next ones
+    pub port: u16,
+    // address sent to the client in REALM_LIST, world.host by default; its host is used for every realm,
+    // its port (if any) only for the first realm, other realms keep their own ports
+    pub realm_address: Option<String>,
+    // accounts in the world, new ones are rejected when the limit is reached (0 means no limit)
+    pub player_limit: usize,
+    // seconds while session key received on login server can be used on world server
+    pub session_ttl: u64,
+    // at-login flags set on every created character to test rename, customize and faction change screens
+    pub character_at_login: u16,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct RealmConfig {
+    pub id: u8,
+    pub name: String,
+    // world server port, Config::realm_port() when omitted
+    pub port: Option<u16>,
+    // address sent in REALM_LIST, Config::realm_address() when omitted
+    pub address: Option<String>,
+    // realm type, see RealmType
+    pub icon: u8,
+    // see RealmFlags
+    pub flags: u8,
+    // realm category from Cfg_Categories.dbc
+    pub timezone: u8,
+    // fixed population (below 1.0 is low, below 2.0 is medium, high otherwise),
+    // computed from online accounts when omitted
+    pub population: Option<f32>,
+    pub locked: bool,
+    pub recommended: bool,
+    pub new_players: bool,
+    // client version shown next to the realm name
+    pub build: Option<RealmBuild>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Features {
+    pub reconnect: bool,
+    pub world_server: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            login: LoginConfig::default(),
+            world: WorldConfig::default(),
+            accounts_path: "accounts.json".to_string(),
+            log_level: "info".to_string(),
+            log_file: None,
+            packet_dumps: Vec::new(),
+            capture_file: None,
+            drain_timeout: 10,
+            features: Features::default(),
+            realms: vec![RealmConfig::default()],
+        }
+    }
+}
+
+impl Default for LoginConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 3724,
+            // 3.3.5a
+            allowed_builds: vec![12340],
+            allowed_platforms: vec!["x86".to_string()],
+        }
+    }
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: 8999,
+            realm_address: None,
+            player_limit: 0,
+            session_ttl: 60 * 60,
+            character_at_login: 0,
+        }
+    }
+}
+
+impl Default for RealmConfig {
+    fn default() -> Self {
+        Self {
+            id: 1,
+            name: "Tine".to_string(),
+            port: None,
+            address: None,
+            icon: 0,
+            flags: 0,
+            timezone: 1,
+            population: None,
+            locked: false,
+            recommended: false,
+            new_players: false,
+            build: None,
+        }
+    }
+}
+
+impl Default for Features {
+    fn default() -> Self {
+        Self {
+            reconnect: true,
+            world_server: true,
+        }
+    }
+}
+
+impl Config {
+    // missing file is fine only when the default path is used
+    pub fn load(path: Option<&str>) -> AnyResult<Self> {
+        let config_path = path.unwrap_or(DEFAULT_CONFIG_PATH);
+        if path.is_none() && !Path::new(config_path).exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(config_path)
+            .with_context(|| format!("Cannot read config file {}", config_path))?;
+
+        serde_json::from_str(&content)
+            .with_context(|| format!("Cannot parse config file {}", config_path))
+    }
+
+    // variable names follow the command line flags: --log-level is TINE_LOG_LEVEL
+    pub fn apply_env(&mut self) -> AnyResult<()> {
+        Self::override_from_env("LOGIN_HOST", &mut self.login.host)?;
+        Self::override_from_env("LOGIN_PORT", &mut self.login.port)?;
+        Self::override_from_env("WORLD_HOST", &mut self.world.host)?;
+        Self::override_from_env("WORLD_PORT", &mut self.world.port)?;
+        Self::override_from_env("PLAYER_LIMIT", &mut self.world.player_limit)?;
+        Self::override_from_env("ACCOUNTS", &mut self.accounts_path)?;
+        Self::override_from_env("LOG_LEVEL", &mut self.log_level)?;
+        Self::override_from_env("DRAIN_TIMEOUT", &mut self.drain_timeout)?;
+
+        if let Ok(log_file) = env::var(format!("{}LOG_FILE", ENV_PREFIX)) {
+            self.log_file = Some(log_file);
+        }
+
+        if let Ok(capture_file) = env::var(format!("{}CAPTURE", ENV_PREFIX)) {
+            self.capture_file = Some(capture_file);
+        }
+
+        if let Ok(realm_address) = env::var(format!("{}REALM_ADDRESS", ENV_PREFIX)) {
+            self.world.realm_address = Some(realm_address);
+        }
+
+        // comma separated opcodes, the same as repeated --dump
+        if let Ok(dumps) = env::var(format!("{}DUMP", ENV_PREFIX)) {
+            self.packet_dumps = dumps.split(',').map(|opcode| opcode.trim().to_string()).collect();
+        }
+
+        Ok(())
+    }
+
+    pub fn validate(&self) -> AnyResult<()> {
+        let mut ids = Vec::new();
+        let mut ports = Vec::new();
+
+        for realm in &self.realms {
+            if ids.contains(&realm.id) {
+                bail!("Realm id {} is used more than once", realm.id);
+            }
+
+            let Some(port) = self.checked_realm_port(realm) else {
+                bail!("World port of realm {} is greater than {}", realm.id, u16::MAX);
+            };
+            if ports.contains(&port) {
+                bail!("World port {} is used by more than one realm", port);
+            }
+
+            ids.push(realm.id);
+            ports.push(port);
+        }
+
+        Ok(())
+    }
+
+    pub fn realm_port(&self, realm: &RealmConfig) -> u16 {
+        self.checked_realm_port(realm).expect("realm ports are checked by Config::validate")
+    }
+
+    // None when world.port plus the realm index does not fit into u16
+    fn checked_realm_port(&self, realm: &RealmConfig) -> Option<u16> {
+        match realm.port {
+            Some(port) => Some(port),
+            None => {
+                let index = self.realms.iter().position(|r| r.id == realm.id).unwrap_or_default();
+                u16::try_from(index).ok().and_then(|index| self.world.port.checked_add(index))
+            },
+        }
+    }
+
+    pub fn realm_address(&self, realm: &RealmConfig) -> String {
+        realm.address.clone().unwrap_or_else(|| {
+            let first = self.realms.first().is_some_and(|first| first.id == realm.id);
+            let host = match (self.world.realm_address.as_deref(), self.world.host.as_str()) {
+                (Some(address), _) if first && address.contains(':') => return address.to_string(),
+                (Some(address), _) => address.split_once(':').map_or(address, |(host, _)| host),
+                (None, "0.0.0.0") => "127.0.0.1",
+                (None, host) => host,
+            };
+
+            format!("{}:{}", host, self.realm_port(realm))
+        })
+    }
+
+    fn override_from_env<T: FromStr>(name: &str, target: &mut T) -> AnyResult<()> {
+        let name = format!("{}{}", ENV_PREFIX, name);
+        if let Ok(value) = env::var(&name) {
+            *target = value.parse().map_err(|_| anyhow!("Invalid value of {}: {}", name, value))?;
+        }
+
+        Ok(())
+    }
+}
+
+// splits "--flag value" and "--flag=value" arguments of the binaries into pairs, usage is shown on errors
+pub fn parse_args(args: impl Iterator<Item = String>, usage: &str) -> AnyResult<Vec<(String, String)>> {
+    let mut parsed = Vec::new();
+    let mut args = args;
+
+    while let Some(arg) = args.next() {
+        let flag = arg.strip_prefix("--").ok_or_else(|| anyhow!("Unexpected argument: {}\n\n{}", arg, usage))?;
+        let (flag, value) = match flag.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), value.to_string()),
+            None if flag == "help" => (flag.to_string(), String::new()),
+            None => (flag.to_string(), args.next().ok_or_else(|| anyhow!("Missing value for --{}", flag))?),
+        };
+
+        parsed.push((flag, value));
+    }
+
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primary::config::{parse_args, Config};
+
+    #[test]
+    fn test_partial_config_uses_defaults() {
+        let config: Config = serde_json::from_str(r#"{ "world": { "port": 9000 } }"#).unwrap();
+
+        assert_eq!(config.login.port, 3724);
+        assert_eq!(config.world.port, 9000);
+        assert_eq!(config.realms.len(), 1);
+        assert_eq!(config.realms[0].name, "Tine");
+        assert_eq!(config.realm_address(&config.realms[0]), "127.0.0.1:9000");
+    }
+
+    #[test]
+    fn test_realm_ports() {
+        let config: Config = serde_json::from_str(r#"{
+            "world": { "realm_address": "10.0.0.1" },
+            "realms": [{ "id": 1 }, { "id": 2 }, { "id": 5, "port": 9500 }]
+        }"#).unwrap();
+
+        assert!(config.validate().is_ok());
+        let addresses: Vec<String> = config.realms.iter().map(|realm| config.realm_address(realm)).collect();
+        assert_eq!(addresses, vec!["10.0.0.1:8999", "10.0.0.1:9000", "10.0.0.1:9500"]);
+
+        // host:port address is kept for the first realm
+        let config: Config = serde_json::from_str(r#"{
+            "world": { "realm_address": "10.0.0.1:7000" },
+            "realms": [{ "id": 1 }, { "id": 2 }]
+        }"#).unwrap();
+        let addresses: Vec<String> = config.realms.iter().map(|realm| config.realm_address(realm)).collect();
+        assert_eq!(addresses, vec!["10.0.0.1:7000", "10.0.0.1:9000"]);
+
+        let config: Config = serde_json::from_str(r#"{ "realms": [{ "id": 1 }, { "id": 2, "port": 8999 }] }"#).unwrap();
+        assert!(config.validate().is_err());
+
+        let config: Config = serde_json::from_str(r#"{ "world": { "port": 65535 }, "realms": [{ "id": 1 }, { "id": 2 }] }"#).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = ["--login-port", "3725", "--capture=session.bin", "--help"].map(String::from);
+        let parsed = parse_args(args.into_iter(), "usage").unwrap();
+
+        assert_eq!(parsed, vec![
+            ("login-port".to_string(), "3725".to_string()),
+            ("capture".to_string(), "session.bin".to_string()),
+            ("help".to_string(), String::new()),
+        ]);
+        assert!(parse_args(["login-port".to_string()].into_iter(), "usage").is_err());
+        assert!(parse_args(["--login-port".to_string()].into_iter(), "usage").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use std::fmt::{Debug, Formatter};
+use hmacsha::HmacSha;
+use sha1::Sha1;
+
+use crate::primary::crypto::rc4::Rc4;
+
+// server encrypts with the key which client uses for decryption and vice versa
+const ENCRYPTION_KEY: [u8; 16] = [
+    0xCC, 0x98, 0xAE, 0x04, 0xE8, 0x97, 0xEA, 0xCA, 0x12, 0xDD, 0xC0, 0x93, 0x42, 0x91, 0x53, 0x57
+];
+
+const DECRYPTION_KEY: [u8; 16] = [
+    0xC2, 0xB3, 0x72, 0x3C, 0xC6, 0xAE, 0xD9, 0xB5, 0x34, 0x3C, 0x53, 0xEE, 0x2F, 0x43, 0x67, 0xCE
+];
+
+const DROP_SIZE: usize = 1024;
+
+pub struct HeaderCrypt {
+    encryptor: Rc4,
+    decryptor: Rc4,
+}
+
+impl HeaderCrypt {
+    pub fn new(session_key: &[u8]) -> Self {
+        Self::with_keys(&ENCRYPTION_KEY, &DECRYPTION_KEY, session_key)
+    }
+
+    // keys of the client side of the connection
+    pub fn client(session_key: &[u8]) -> Self {
+        Self::with_keys(&DECRYPTION_KEY, &ENCRYPTION_KEY, session_key)
+    }
+
+    pub fn encrypt(&mut self, header: &mut [u8]) {
+        self.encryptor.apply(header);
+    }
+
+    pub fn decrypt(&mut self, header: &mut [u8]) {
+        self.decryptor.apply(header);
+    }
+
+    fn with_keys(encryption_key: &[u8], decryption_key: &[u8], session_key: &[u8]) -> Self {
+        Self {
+            encryptor: Self::create_cipher(encryption_key, session_key),
+            decryptor: Self::create_cipher(decryption_key, session_key),
+        }
+    }
+
+    fn create_cipher(key: &[u8], session_key: &[u8]) -> Rc4 {
+        let digest = HmacSha::new(key, session_key, Sha1::default()).compute_digest();
+
+        let mut cipher = Rc4::new(&digest);
+        cipher.apply(&mut [0u8; DROP_SIZE]);
+
+        cipher
+    }
+}
+
+impl Debug for HeaderCrypt {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HeaderCrypt")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primary::crypto::header_crypt::HeaderCrypt;
+
+    #[test]
+    fn test_client_can_read_server_headers() {
+        let session_key = [rand::random::<[u8; 32]>(), rand::random::<[u8; 32]>()].concat();
+        let mut server = HeaderCrypt::new(&session_key);
+        let mut client = HeaderCrypt::client(&session_key);
+
+        for header in [[0, 7, 0xEE, 0x01], [0, 5, 0x01, 0x02]] {
+            let mut encrypted = header;
+            server.encrypt(&mut encrypted);
+            assert_ne!(encrypted, header);
+
+            client.decrypt(&mut encrypted);
+            assert_eq!(encrypted, header);
+        }
+
+        let mut client_header = [0, 4, 0xDC, 0x01, 0, 0];
+        client.encrypt(&mut client_header);
+        server.decrypt(&mut client_header);
+        assert_eq!(client_header, [0, 4, 0xDC, 0x01, 0, 0]);
+    }
+}
@@ -1 +1,3 @@
-pub mod srp;
\ No newline at end of file
+pub mod header_crypt;
+pub mod rc4;
+pub mod srp;
@@ -17,6 +17,12 @@ pub struct Srp {
     verifier: Option<BigInt>
 }
 
+impl Default for Srp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // public methods
 impl Srp {
     pub fn new() -> Self {
@@ -27,7 +33,7 @@ impl Srp {
         Self {
             modulus,
             generator,
-            salt: rand::random(),
+            salt: [0u8; 32],
             server_ephemeral: None,
             session_key: None,
             multiplier: BigInt::from_i32(3).unwrap(),
@@ -52,8 +58,8 @@ impl Srp {
             .to_vec()
     }
 
-    pub fn generate_verifier<D>(&mut self) where D: Digest {
-        let x = self.calculate_x::<D>();
+    pub fn generate_verifier<D>(&mut self, password: &str) where D: Digest {
+        let x = self.calculate_x::<D>(password);
         let verifier = self.generator.modpow(
             &x,
             &self.modulus,
@@ -61,7 +67,11 @@ impl Srp {
         self.verifier = Some(verifier);
     }
 
-    pub fn generate_server_ephemeral<D>(&mut self) where D: Digest {
+    pub fn verifier(&self) -> Option<Vec<u8>> {
+        self.verifier.as_ref().map(|verifier| verifier.to_bytes_le().1)
+    }
+
+    pub fn generate_server_ephemeral(&mut self) {
         let v = self.verifier.as_ref().unwrap();
         let big_integer = self.generator.modpow(&self.private_ephemeral, &self.modulus);
         self.server_ephemeral = Some((&self.multiplier * v + &big_integer) % &self.modulus);
@@ -83,6 +93,35 @@ impl Srp {
     pub fn set_account(&mut self, account: String) {
         self.account = Some(account.to_uppercase());
     }
+
+    pub fn set_salt(&mut self, salt: [u8; 32]) {
+        self.salt = salt;
+    }
+
+    pub fn set_verifier(&mut self, verifier: &[u8]) {
+        self.verifier = Some(BigInt::from_bytes_le(Sign::Plus, verifier));
+    }
+
+    pub fn set_server_ephemeral(&mut self, server_ephemeral: &[u8]) {
+        self.server_ephemeral = Some(BigInt::from_bytes_le(Sign::Plus, server_ephemeral));
+    }
+
+    // client side of the exchange, used to replay captured logins
+    pub fn client_ephemeral(&self) -> Vec<u8> {
+        self.generator.modpow(&self.private_ephemeral, &self.modulus).to_bytes_le().1
+    }
+
+    pub fn calculate_client_session_key<D>(&mut self, password: &str, client_ephemeral: &[u8]) where D: Digest {
+        let x = self.calculate_x::<D>(password);
+        let u = self.calculate_u::<D>(client_ephemeral);
+        let server_ephemeral = self.server_ephemeral.as_ref().unwrap();
+
+        let gx = self.generator.modpow(&x, &self.modulus);
+        let base = (server_ephemeral + &self.multiplier * (&self.modulus - gx)) % &self.modulus;
+        let s = base.modpow(&(&self.private_ephemeral + u * x), &self.modulus);
+
+        self.session_key = Some(Self::calculate_interleaved::<D>(s));
+    }
 }
 
 // private methods
@@ -113,13 +152,13 @@ impl Srp {
         xor_hash
     }
 
-    fn calculate_x<D>(&mut self) -> BigInt
+    fn calculate_x<D>(&mut self, password: &str) -> BigInt
         where
             D: Digest,
     {
         let account = self.account.as_ref().unwrap();
         let identity_hash = D::new()
-            .chain(format!("{}:{}", account, account).as_bytes())
+            .chain(format!("{}:{}", account, password.to_uppercase()).as_bytes())
             .finalize()
             .to_vec();
 
@@ -175,4 +214,92 @@ impl Srp {
         let random_bytes: [u8; 19] = rand::random();
         BigInt::from_bytes_le(Sign::Plus, &random_bytes)
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use num_bigint::{BigInt, Sign};
+    use sha1::{Digest, Sha1};
+
+    use crate::primary::crypto::srp::Srp;
+
+    #[test]
+    fn test_client_and_server_proofs_match() {
+        let salt: [u8; 32] = rand::random();
+
+        let mut account = Srp::new();
+        account.set_account("Test".to_string());
+        account.set_salt(salt);
+        account.generate_verifier::<Sha1>("Password");
+        let verifier = account.verifier().unwrap();
+
+        let mut server = Srp::new();
+        server.set_account("TEST".to_string());
+        server.set_salt(salt);
+        server.set_verifier(&verifier);
+        server.generate_server_ephemeral();
+
+        // client side of the exchange
+        let private_ephemeral = BigInt::from_bytes_le(Sign::Plus, &rand::random::<[u8; 19]>());
+        let client_ephemeral = server.generator
+            .modpow(&private_ephemeral, &server.modulus)
+            .to_bytes_le().1;
+        let server_ephemeral = server.server_ephemeral.clone().unwrap();
+
+        let x = BigInt::from_bytes_le(Sign::Plus, &Sha1::new()
+            .chain(salt)
+            .chain(Sha1::new().chain("TEST:PASSWORD").finalize())
+            .finalize());
+        let u = BigInt::from_bytes_le(Sign::Plus, &Sha1::new()
+            .chain(&client_ephemeral)
+            .chain(server_ephemeral.to_bytes_le().1)
+            .finalize());
+        let base = {
+            let gx = server.generator.modpow(&x, &server.modulus);
+            (&server_ephemeral + &server.multiplier * (&server.modulus - gx)) % &server.modulus
+        };
+        let s = base.modpow(&(&private_ephemeral + &u * &x), &server.modulus);
+        let client_session_key = Srp::calculate_interleaved::<Sha1>(s);
+
+        server.calculate_session_key::<Sha1>(&client_ephemeral);
+        assert_eq!(server.session_key.clone().unwrap(), client_session_key);
+
+        let client_proof = Sha1::new()
+            .chain(server.calculate_xor_hash::<Sha1>())
+            .chain(Sha1::new().chain("TEST").finalize())
+            .chain(salt)
+            .chain(&client_ephemeral)
+            .chain(server_ephemeral.to_bytes_le().1)
+            .chain(&client_session_key)
+            .finalize()
+            .to_vec();
+        assert_eq!(server.calculate_proof::<Sha1>(&client_ephemeral), client_proof);
+    }
+
+    #[test]
+    fn test_client_session_key() {
+        let salt: [u8; 32] = rand::random();
+
+        let mut account = Srp::new();
+        account.set_account("test".to_string());
+        account.set_salt(salt);
+        account.generate_verifier::<Sha1>("password");
+
+        let mut server = Srp::new();
+        server.set_account("TEST".to_string());
+        server.set_salt(salt);
+        server.set_verifier(&account.verifier().unwrap());
+        server.generate_server_ephemeral();
+
+        let mut client = Srp::new();
+        client.set_account("test".to_string());
+        client.set_salt(salt);
+        client.set_server_ephemeral(&server.server_ephemeral.as_ref().unwrap().to_bytes_le().1);
+
+        let client_ephemeral = client.client_ephemeral();
+        client.calculate_client_session_key::<Sha1>("password", &client_ephemeral);
+        server.calculate_session_key::<Sha1>(&client_ephemeral);
+
+        assert_eq!(client.session_key, server.session_key);
+        assert_eq!(client.calculate_proof::<Sha1>(&client_ephemeral), server.calculate_proof::<Sha1>(&client_ephemeral));
+    }
+}
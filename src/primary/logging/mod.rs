@@ -0,0 +1,86 @@
+use std::fmt::Write;
+use std::fs::OpenOptions;
+use std::sync::{Mutex as SyncMutex};
+use anyhow::{anyhow, Context, Result as AnyResult};
+use tracing_subscriber::EnvFilter;
+
+use crate::primary::config::Config;
+
+// target of packet dumps, can be used in log_level to filter them
+pub const PACKETS_TARGET: &str = "packets";
+// dumps packets of every opcode when listed in config.packet_dumps
+const ALL_OPCODES: &str = "*";
+const BYTES_PER_LINE: usize = 16;
+
+pub fn init(config: &Config) -> AnyResult<()> {
+    let filter = EnvFilter::try_new(&config.log_level)
+        .with_context(|| format!("Invalid log level: {}", config.log_level))?;
+    let builder = tracing_subscriber::fmt().with_env_filter(filter);
+
+    match &config.log_file {
+        Some(path) => {
+            let file = OpenOptions::new().create(true).append(true).open(path)
+                .with_context(|| format!("Cannot open log file {}", path))?;
+            builder.with_ansi(false).with_writer(SyncMutex::new(file)).try_init()
+        },
+        None => builder.try_init(),
+    }.map_err(|e| anyhow!(e))
+}
+
+pub fn should_dump(config: &Config, opcode_name: &str) -> bool {
+    config.packet_dumps.iter().any(|name| name == ALL_OPCODES || name.eq_ignore_ascii_case(opcode_name))
+}
+
+// offset, hex bytes and printable characters, 16 bytes per line
+pub fn hex_dump(data: &[u8]) -> String {
+    let mut output = String::new();
+
+    for (index, chunk) in data.chunks(BYTES_PER_LINE).enumerate() {
+        if index > 0 {
+            output.push('\n');
+        }
+
+        let _ = write!(output, "{:04X}  ", index * BYTES_PER_LINE);
+        for byte in chunk {
+            let _ = write!(output, "{:02X} ", byte);
+        }
+        output.push_str(&"   ".repeat(BYTES_PER_LINE - chunk.len()));
+        output.push(' ');
+        output.extend(chunk.iter().map(|&byte| match byte.is_ascii_graphic() {
+            true => byte as char,
+            false => '.',
+        }));
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primary::config::Config;
+    use crate::primary::logging::{hex_dump, should_dump};
+
+    #[test]
+    fn test_hex_dump() {
+        let data: Vec<u8> = (0x41..0x53).collect();
+
+        assert_eq!(
+            hex_dump(&data),
+            "0000  41 42 43 44 45 46 47 48 49 4A 4B 4C 4D 4E 4F 50  ABCDEFGHIJKLMNOP\n\
+             0010  51 52                                            QR",
+        );
+    }
+
+    #[test]
+    fn test_should_dump() {
+        let mut config = Config::default();
+        assert!(!should_dump(&config, "CMSG_AUTH_SESSION"));
+
+        config.packet_dumps = vec!["cmsg_auth_session".to_string()];
+        assert!(should_dump(&config, "CMSG_AUTH_SESSION"));
+        assert!(!should_dump(&config, "SMSG_AUTH_RESPONSE"));
+
+        config.packet_dumps = vec!["*".to_string()];
+        assert!(should_dump(&config, "SMSG_AUTH_RESPONSE"));
+    }
+}
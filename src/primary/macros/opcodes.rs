@@ -17,6 +17,7 @@ macro_rules! opcodes {
                 pub const $const_name: $const_type = $const_value;
             )*
 
+            #[allow(dead_code)]
             pub fn get_opcode_name(index: u32) -> Option<String> {
                 match index {
                     $(
@@ -25,6 +26,29 @@ macro_rules! opcodes {
                     _ => None,
                 }
             }
+
+            // login and world opcodes overlap, so the lookup is limited by the opcode type
+            pub fn get_login_opcode_name(index: u32) -> Option<String> {
+                match index {
+                    $(
+                        $const_value if stringify!($const_type) == "u8" && $const_value == $const_value as u32 => {
+                            Some(stringify!($const_name).to_string())
+                        },
+                    )*
+                    _ => None,
+                }
+            }
+
+            pub fn get_world_opcode_name(index: u32) -> Option<String> {
+                match index {
+                    $(
+                        $const_value if stringify!($const_type) != "u8" && $const_value == $const_value as u32 => {
+                            Some(stringify!($const_name).to_string())
+                        },
+                    )*
+                    _ => None,
+                }
+            }
         }
     };
 }
\ No newline at end of file
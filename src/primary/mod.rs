@@ -3,4 +3,12 @@ pub mod macros;
 pub mod traits;
 pub mod types;
 pub mod serializers;
-pub mod crypto;
\ No newline at end of file
+pub mod crypto;
+pub mod accounts;
+pub mod characters;
+pub mod codecs;
+pub mod capture;
+pub mod config;
+pub mod logging;
+pub mod replay;
+pub mod objects;
\ No newline at end of file
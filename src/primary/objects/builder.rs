@@ -0,0 +1,167 @@
+use anyhow::Result as AnyResult;
+use serde::{Deserialize, Serialize};
+use tentacli::packet::idewave::WorldPacket;
+
+use crate::with_opcode;
+use crate::primary::objects::WorldObject;
+use crate::primary::server::Opcode;
+use crate::primary::types::OutgoingPacket;
+use crate::primary::types::fields::update_blocks::{CreateBlock, MovementBlock, UpdateBlock, UpdateBlocks};
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_UPDATE_OBJECT)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct UpdateObject {
+        blocks: UpdateBlocks,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_DESTROY_OBJECT)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct DestroyObject {
+        guid: u64,
+        // the client plays the death animation
+        on_death: u8,
+    }
+}
+
+// collects the state changes of the objects and emits SMSG_UPDATE_OBJECT with them,
+// 3.3.5a has no destroy block, so destroyed objects are sent with SMSG_DESTROY_OBJECT after it
+#[derive(Default)]
+pub struct UpdateObjectBuilder {
+    blocks: Vec<UpdateBlock>,
+    destroyed: Vec<(u64, bool)>,
+}
+
+impl UpdateObjectBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // object which is already in the world and comes into the visibility range
+    pub fn create(mut self, object: &mut WorldObject, movement: MovementBlock) -> Self {
+        self.blocks.push(UpdateBlock::CreateObject(Self::create_block(object, movement)));
+        self
+    }
+
+    // object which appears in the world for the first time
+    pub fn spawn(mut self, object: &mut WorldObject, movement: MovementBlock) -> Self {
+        self.blocks.push(UpdateBlock::SpawnObject(Self::create_block(object, movement)));
+        self
+    }
+
+    // fields changed since the last update, nothing is added when the object has no changes
+    pub fn values(mut self, object: &mut WorldObject) -> Self {
+        if object.has_changes() {
+            self.blocks.push(UpdateBlock::Values { guid: object.guid, values: object.changes() });
+        }
+        self
+    }
+
+    pub fn out_of_range(mut self, guids: Vec<u64>) -> Self {
+        if !guids.is_empty() {
+            self.blocks.push(UpdateBlock::OutOfRange { guids });
+        }
+        self
+    }
+
+    pub fn destroy(mut self, guid: u64, on_death: bool) -> Self {
+        self.destroyed.push((guid, on_death));
+        self
+    }
+
+    pub fn build(self) -> AnyResult<Vec<OutgoingPacket>> {
+        let mut packets = Vec::new();
+
+        if !self.blocks.is_empty() {
+            packets.push(UpdateObject { blocks: UpdateBlocks(self.blocks) }.unpack()?);
+        }
+
+        for (guid, on_death) in self.destroyed {
+            packets.push(DestroyObject { guid, on_death: on_death as u8 }.unpack()?);
+        }
+
+        Ok(packets)
+    }
+
+    // created object gets all its values, so they are not sent again with the next update
+    fn create_block(object: &mut WorldObject, movement: MovementBlock) -> CreateBlock {
+        object.changes();
+
+        CreateBlock {
+            guid: object.guid,
+            object_type: object.object_type,
+            movement,
+            values: object.values(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tentacli::traits::BinaryConverter;
+
+    use crate::primary::objects::{UpdateFlags, UpdateObjectBuilder, WorldObject};
+    use crate::primary::objects::fields::UnitFields;
+    use crate::primary::server::Opcode;
+    use crate::primary::types::fields::update_blocks::{
+        LivingMovement, MovementBlock, UpdateBlock, UpdateBlocks,
+    };
+
+    #[test]
+    fn test_build() {
+        let mut player = WorldObject::player(1);
+        player.set_u32(UnitFields::HEALTH, 100);
+
+        let movement = MovementBlock {
+            update_flags: UpdateFlags::SELF | UpdateFlags::LIVING,
+            living: Some(LivingMovement::default()),
+            ..MovementBlock::default()
+        };
+
+        let packets = UpdateObjectBuilder::new()
+            .spawn(&mut player, movement)
+            .values(&mut player)
+            .out_of_range(vec![2])
+            .destroy(3, true)
+            .build()
+            .unwrap();
+
+        assert_eq!(packets.len(), 2);
+        assert_eq!(packets[0].opcode, Opcode::SMSG_UPDATE_OBJECT as u32);
+        assert_eq!(packets[1].opcode, Opcode::SMSG_DESTROY_OBJECT as u32);
+        assert_eq!(packets[1].data[6..], [3, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+        // values are sent with the create block only
+        let blocks = UpdateBlocks::read_from(&packets[0].data[6..]).unwrap().0;
+        assert_eq!(blocks.len(), 2);
+        match &blocks[0] {
+            UpdateBlock::SpawnObject(create) => {
+                assert_eq!(create.guid, 1);
+                assert_eq!(create.values.fields[&UnitFields::HEALTH], 100);
+            },
+            block => panic!("Unexpected block: {:?}", block),
+        }
+        assert_eq!(blocks[1], UpdateBlock::OutOfRange { guids: vec![2] });
+    }
+
+    #[test]
+    fn test_values_update() {
+        let mut unit = WorldObject::unit(5);
+        unit.changes();
+        assert!(UpdateObjectBuilder::new().values(&mut unit).build().unwrap().is_empty());
+
+        unit.set_u32(UnitFields::LEVEL, 10);
+        let packets = UpdateObjectBuilder::new().values(&mut unit).build().unwrap();
+        let blocks = UpdateBlocks::read_from(&packets[0].data[6..]).unwrap().0;
+
+        match &blocks[0] {
+            UpdateBlock::Values { guid, values } => {
+                assert_eq!(*guid, 5);
+                assert_eq!(values.fields.keys().copied().collect::<Vec<u16>>(), vec![UnitFields::LEVEL]);
+            },
+            block => panic!("Unexpected block: {:?}", block),
+        }
+    }
+}
@@ -0,0 +1,201 @@
+// update field indexes of 3.3.5a, every field takes 4 bytes, 64-bit values take two of them
+
+// fields of every object
+#[non_exhaustive]
+pub struct ObjectFields;
+
+#[allow(dead_code)]
+impl ObjectFields {
+    pub const GUID: u16    = 0x0000; // size 2
+    pub const TYPE: u16    = 0x0002;
+    pub const ENTRY: u16   = 0x0003;
+    pub const SCALE_X: u16 = 0x0004;
+    pub const PADDING: u16 = 0x0005;
+    pub const END: u16     = 0x0006;
+}
+
+// fields of creatures and players following ObjectFields
+#[non_exhaustive]
+pub struct UnitFields;
+
+#[allow(dead_code, clippy::identity_op)]
+impl UnitFields {
+    pub const CHARM: u16                                 = ObjectFields::END + 0x0000; // size 2
+    pub const SUMMON: u16                                = ObjectFields::END + 0x0002; // size 2
+    pub const CRITTER: u16                               = ObjectFields::END + 0x0004; // size 2
+    pub const CHARMED_BY: u16                            = ObjectFields::END + 0x0006; // size 2
+    pub const SUMMONED_BY: u16                           = ObjectFields::END + 0x0008; // size 2
+    pub const CREATED_BY: u16                            = ObjectFields::END + 0x000A; // size 2
+    pub const TARGET: u16                                = ObjectFields::END + 0x000C; // size 2
+    pub const CHANNEL_OBJECT: u16                        = ObjectFields::END + 0x000E; // size 2
+    pub const CHANNEL_SPELL: u16                         = ObjectFields::END + 0x0010;
+    pub const BYTES_0: u16                               = ObjectFields::END + 0x0011;
+    pub const HEALTH: u16                                = ObjectFields::END + 0x0012;
+    pub const POWER1: u16                                = ObjectFields::END + 0x0013;
+    pub const POWER2: u16                                = ObjectFields::END + 0x0014;
+    pub const POWER3: u16                                = ObjectFields::END + 0x0015;
+    pub const POWER4: u16                                = ObjectFields::END + 0x0016;
+    pub const POWER5: u16                                = ObjectFields::END + 0x0017;
+    pub const POWER6: u16                                = ObjectFields::END + 0x0018;
+    pub const POWER7: u16                                = ObjectFields::END + 0x0019;
+    pub const MAX_HEALTH: u16                            = ObjectFields::END + 0x001A;
+    pub const MAX_POWER1: u16                            = ObjectFields::END + 0x001B;
+    pub const MAX_POWER2: u16                            = ObjectFields::END + 0x001C;
+    pub const MAX_POWER3: u16                            = ObjectFields::END + 0x001D;
+    pub const MAX_POWER4: u16                            = ObjectFields::END + 0x001E;
+    pub const MAX_POWER5: u16                            = ObjectFields::END + 0x001F;
+    pub const MAX_POWER6: u16                            = ObjectFields::END + 0x0020;
+    pub const MAX_POWER7: u16                            = ObjectFields::END + 0x0021;
+    pub const POWER_REGEN_FLAT_MODIFIER: u16             = ObjectFields::END + 0x0022; // size 7
+    pub const POWER_REGEN_INTERRUPTED_FLAT_MODIFIER: u16 = ObjectFields::END + 0x0029; // size 7
+    pub const LEVEL: u16                                 = ObjectFields::END + 0x0030;
+    pub const FACTION_TEMPLATE: u16                      = ObjectFields::END + 0x0031;
+    pub const VIRTUAL_ITEM_SLOT_ID: u16                  = ObjectFields::END + 0x0032; // size 3
+    pub const FLAGS: u16                                 = ObjectFields::END + 0x0035;
+    pub const FLAGS_2: u16                               = ObjectFields::END + 0x0036;
+    pub const AURA_STATE: u16                            = ObjectFields::END + 0x0037;
+    pub const BASE_ATTACK_TIME: u16                      = ObjectFields::END + 0x0038; // size 2
+    pub const RANGED_ATTACK_TIME: u16                    = ObjectFields::END + 0x003A;
+    pub const BOUNDING_RADIUS: u16                       = ObjectFields::END + 0x003B;
+    pub const COMBAT_REACH: u16                          = ObjectFields::END + 0x003C;
+    pub const DISPLAY_ID: u16                            = ObjectFields::END + 0x003D;
+    pub const NATIVE_DISPLAY_ID: u16                     = ObjectFields::END + 0x003E;
+    pub const MOUNT_DISPLAY_ID: u16                      = ObjectFields::END + 0x003F;
+    pub const MIN_DAMAGE: u16                            = ObjectFields::END + 0x0040;
+    pub const MAX_DAMAGE: u16                            = ObjectFields::END + 0x0041;
+    pub const MIN_OFFHAND_DAMAGE: u16                    = ObjectFields::END + 0x0042;
+    pub const MAX_OFFHAND_DAMAGE: u16                    = ObjectFields::END + 0x0043;
+    pub const BYTES_1: u16                               = ObjectFields::END + 0x0044;
+    pub const PET_NUMBER: u16                            = ObjectFields::END + 0x0045;
+    pub const PET_NAME_TIMESTAMP: u16                    = ObjectFields::END + 0x0046;
+    pub const PET_EXPERIENCE: u16                        = ObjectFields::END + 0x0047;
+    pub const PET_NEXT_LEVEL_EXP: u16                    = ObjectFields::END + 0x0048;
+    pub const DYNAMIC_FLAGS: u16                         = ObjectFields::END + 0x0049;
+    pub const MOD_CAST_SPEED: u16                        = ObjectFields::END + 0x004A;
+    pub const CREATED_BY_SPELL: u16                      = ObjectFields::END + 0x004B;
+    pub const NPC_FLAGS: u16                             = ObjectFields::END + 0x004C;
+    pub const NPC_EMOTE_STATE: u16                       = ObjectFields::END + 0x004D;
+    pub const STAT0: u16                                 = ObjectFields::END + 0x004E;
+    pub const STAT1: u16                                 = ObjectFields::END + 0x004F;
+    pub const STAT2: u16                                 = ObjectFields::END + 0x0050;
+    pub const STAT3: u16                                 = ObjectFields::END + 0x0051;
+    pub const STAT4: u16                                 = ObjectFields::END + 0x0052;
+    pub const POS_STAT0: u16                             = ObjectFields::END + 0x0053;
+    pub const POS_STAT1: u16                             = ObjectFields::END + 0x0054;
+    pub const POS_STAT2: u16                             = ObjectFields::END + 0x0055;
+    pub const POS_STAT3: u16                             = ObjectFields::END + 0x0056;
+    pub const POS_STAT4: u16                             = ObjectFields::END + 0x0057;
+    pub const NEG_STAT0: u16                             = ObjectFields::END + 0x0058;
+    pub const NEG_STAT1: u16                             = ObjectFields::END + 0x0059;
+    pub const NEG_STAT2: u16                             = ObjectFields::END + 0x005A;
+    pub const NEG_STAT3: u16                             = ObjectFields::END + 0x005B;
+    pub const NEG_STAT4: u16                             = ObjectFields::END + 0x005C;
+    pub const RESISTANCES: u16                           = ObjectFields::END + 0x005D; // size 7
+    pub const RESISTANCE_BUFF_MODS_POSITIVE: u16         = ObjectFields::END + 0x0064; // size 7
+    pub const RESISTANCE_BUFF_MODS_NEGATIVE: u16         = ObjectFields::END + 0x006B; // size 7
+    pub const BASE_MANA: u16                             = ObjectFields::END + 0x0072;
+    pub const BASE_HEALTH: u16                           = ObjectFields::END + 0x0073;
+    pub const BYTES_2: u16                               = ObjectFields::END + 0x0074;
+    pub const ATTACK_POWER: u16                          = ObjectFields::END + 0x0075;
+    pub const ATTACK_POWER_MODS: u16                     = ObjectFields::END + 0x0076;
+    pub const ATTACK_POWER_MULTIPLIER: u16               = ObjectFields::END + 0x0077;
+    pub const RANGED_ATTACK_POWER: u16                   = ObjectFields::END + 0x0078;
+    pub const RANGED_ATTACK_POWER_MODS: u16              = ObjectFields::END + 0x0079;
+    pub const RANGED_ATTACK_POWER_MULTIPLIER: u16        = ObjectFields::END + 0x007A;
+    pub const MIN_RANGED_DAMAGE: u16                     = ObjectFields::END + 0x007B;
+    pub const MAX_RANGED_DAMAGE: u16                     = ObjectFields::END + 0x007C;
+    pub const POWER_COST_MODIFIER: u16                   = ObjectFields::END + 0x007D; // size 7
+    pub const POWER_COST_MULTIPLIER: u16                 = ObjectFields::END + 0x0084; // size 7
+    pub const MAX_HEALTH_MODIFIER: u16                   = ObjectFields::END + 0x008B;
+    pub const HOVER_HEIGHT: u16                          = ObjectFields::END + 0x008C;
+    pub const PADDING: u16                               = ObjectFields::END + 0x008D;
+    pub const END: u16                                   = ObjectFields::END + 0x008E;
+}
+
+// fields of players following UnitFields
+#[non_exhaustive]
+pub struct PlayerFields;
+
+#[allow(dead_code, clippy::identity_op)]
+impl PlayerFields {
+    pub const DUEL_ARBITER: u16                   = UnitFields::END + 0x0000; // size 2
+    pub const FLAGS: u16                          = UnitFields::END + 0x0002;
+    pub const GUILD_ID: u16                       = UnitFields::END + 0x0003;
+    pub const GUILD_RANK: u16                     = UnitFields::END + 0x0004;
+    pub const BYTES: u16                          = UnitFields::END + 0x0005;
+    pub const BYTES_2: u16                        = UnitFields::END + 0x0006;
+    pub const BYTES_3: u16                        = UnitFields::END + 0x0007;
+    pub const DUEL_TEAM: u16                      = UnitFields::END + 0x0008;
+    pub const GUILD_TIMESTAMP: u16                = UnitFields::END + 0x0009;
+    pub const QUEST_LOG_1_1: u16                  = UnitFields::END + 0x000A; // size 125
+    pub const VISIBLE_ITEM_1_ENTRY_ID: u16        = UnitFields::END + 0x0087; // size 38
+    pub const CHOSEN_TITLE: u16                   = UnitFields::END + 0x00AD;
+    pub const FAKE_INEBRIATION: u16               = UnitFields::END + 0x00AE;
+    pub const PADDING_0: u16                      = UnitFields::END + 0x00AF;
+    pub const INV_SLOT_HEAD: u16                  = UnitFields::END + 0x00B0; // size 46
+    pub const PACK_SLOT_1: u16                    = UnitFields::END + 0x00DE; // size 32
+    pub const BANK_SLOT_1: u16                    = UnitFields::END + 0x00FE; // size 56
+    pub const BANKBAG_SLOT_1: u16                 = UnitFields::END + 0x0136; // size 14
+    pub const VENDOR_BUYBACK_SLOT_1: u16          = UnitFields::END + 0x0144; // size 24
+    pub const KEYRING_SLOT_1: u16                 = UnitFields::END + 0x015C; // size 64
+    pub const CURRENCY_TOKEN_SLOT_1: u16          = UnitFields::END + 0x019C; // size 64
+    pub const FARSIGHT: u16                       = UnitFields::END + 0x01DC; // size 2
+    pub const KNOWN_TITLES: u16                   = UnitFields::END + 0x01DE; // size 2
+    pub const KNOWN_TITLES1: u16                  = UnitFields::END + 0x01E0; // size 2
+    pub const KNOWN_TITLES2: u16                  = UnitFields::END + 0x01E2; // size 2
+    pub const KNOWN_CURRENCIES: u16               = UnitFields::END + 0x01E4; // size 2
+    pub const XP: u16                             = UnitFields::END + 0x01E6;
+    pub const NEXT_LEVEL_XP: u16                  = UnitFields::END + 0x01E7;
+    pub const SKILL_INFO_1_1: u16                 = UnitFields::END + 0x01E8; // size 384
+    pub const CHARACTER_POINTS1: u16              = UnitFields::END + 0x0368;
+    pub const CHARACTER_POINTS2: u16              = UnitFields::END + 0x0369;
+    pub const TRACK_CREATURES: u16                = UnitFields::END + 0x036A;
+    pub const TRACK_RESOURCES: u16                = UnitFields::END + 0x036B;
+    pub const BLOCK_PERCENTAGE: u16               = UnitFields::END + 0x036C;
+    pub const DODGE_PERCENTAGE: u16               = UnitFields::END + 0x036D;
+    pub const PARRY_PERCENTAGE: u16               = UnitFields::END + 0x036E;
+    pub const EXPERTISE: u16                      = UnitFields::END + 0x036F;
+    pub const OFFHAND_EXPERTISE: u16              = UnitFields::END + 0x0370;
+    pub const CRIT_PERCENTAGE: u16                = UnitFields::END + 0x0371;
+    pub const RANGED_CRIT_PERCENTAGE: u16         = UnitFields::END + 0x0372;
+    pub const OFFHAND_CRIT_PERCENTAGE: u16        = UnitFields::END + 0x0373;
+    pub const SPELL_CRIT_PERCENTAGE1: u16         = UnitFields::END + 0x0374; // size 7
+    pub const SHIELD_BLOCK: u16                   = UnitFields::END + 0x037B;
+    pub const SHIELD_BLOCK_CRIT_PERCENTAGE: u16   = UnitFields::END + 0x037C;
+    pub const EXPLORED_ZONES_1: u16               = UnitFields::END + 0x037D; // size 128
+    pub const REST_STATE_EXPERIENCE: u16          = UnitFields::END + 0x03FD;
+    pub const COINAGE: u16                        = UnitFields::END + 0x03FE;
+    pub const MOD_DAMAGE_DONE_POS: u16            = UnitFields::END + 0x03FF; // size 7
+    pub const MOD_DAMAGE_DONE_NEG: u16            = UnitFields::END + 0x0406; // size 7
+    pub const MOD_DAMAGE_DONE_PCT: u16            = UnitFields::END + 0x040D; // size 7
+    pub const MOD_HEALING_DONE_POS: u16           = UnitFields::END + 0x0414;
+    pub const MOD_HEALING_PCT: u16                = UnitFields::END + 0x0415;
+    pub const MOD_HEALING_DONE_PCT: u16           = UnitFields::END + 0x0416;
+    pub const MOD_TARGET_RESISTANCE: u16          = UnitFields::END + 0x0417;
+    pub const MOD_TARGET_PHYSICAL_RESISTANCE: u16 = UnitFields::END + 0x0418;
+    pub const FIELD_BYTES: u16                    = UnitFields::END + 0x0419;
+    pub const AMMO_ID: u16                        = UnitFields::END + 0x041A;
+    pub const SELF_RES_SPELL: u16                 = UnitFields::END + 0x041B;
+    pub const PVP_MEDALS: u16                     = UnitFields::END + 0x041C;
+    pub const BUYBACK_PRICE_1: u16                = UnitFields::END + 0x041D; // size 12
+    pub const BUYBACK_TIMESTAMP_1: u16            = UnitFields::END + 0x0429; // size 12
+    pub const KILLS: u16                          = UnitFields::END + 0x0435;
+    pub const TODAY_CONTRIBUTION: u16             = UnitFields::END + 0x0436;
+    pub const YESTERDAY_CONTRIBUTION: u16         = UnitFields::END + 0x0437;
+    pub const LIFETIME_HONORABLE_KILLS: u16       = UnitFields::END + 0x0438;
+    pub const FIELD_BYTES2: u16                   = UnitFields::END + 0x0439;
+    pub const WATCHED_FACTION_INDEX: u16          = UnitFields::END + 0x043A;
+    pub const COMBAT_RATING_1: u16                = UnitFields::END + 0x043B; // size 25
+    pub const ARENA_TEAM_INFO_1_1: u16            = UnitFields::END + 0x0454; // size 21
+    pub const HONOR_CURRENCY: u16                 = UnitFields::END + 0x0469;
+    pub const ARENA_CURRENCY: u16                 = UnitFields::END + 0x046A;
+    pub const MAX_LEVEL: u16                      = UnitFields::END + 0x046B;
+    pub const DAILY_QUESTS_1: u16                 = UnitFields::END + 0x046C; // size 25
+    pub const RUNE_REGEN_1: u16                   = UnitFields::END + 0x0485; // size 4
+    pub const NO_REAGENT_COST_1: u16              = UnitFields::END + 0x0489; // size 3
+    pub const GLYPH_SLOTS_1: u16                  = UnitFields::END + 0x048C; // size 6
+    pub const GLYPHS_1: u16                       = UnitFields::END + 0x0492; // size 6
+    pub const GLYPHS_ENABLED: u16                 = UnitFields::END + 0x0498;
+    pub const PET_SPELL_POWER: u16                = UnitFields::END + 0x0499;
+    pub const END: u16                            = UnitFields::END + 0x049A;
+}
@@ -0,0 +1,8 @@
+pub mod fields;
+pub mod types;
+mod builder;
+mod world_object;
+
+pub use builder::UpdateObjectBuilder;
+pub use types::{ObjectType, TypeMask, UpdateFlags, UpdateType};
+pub use world_object::WorldObject;
@@ -0,0 +1,62 @@
+#[non_exhaustive]
+pub struct ObjectType;
+
+#[allow(dead_code)]
+impl ObjectType {
+    pub const OBJECT: u8         = 0;
+    pub const ITEM: u8           = 1;
+    pub const CONTAINER: u8      = 2;
+    pub const UNIT: u8           = 3;
+    pub const PLAYER: u8         = 4;
+    pub const GAME_OBJECT: u8    = 5;
+    pub const DYNAMIC_OBJECT: u8 = 6;
+    pub const CORPSE: u8         = 7;
+}
+
+// written to ObjectFields::TYPE, every object type includes the masks of its parents
+#[non_exhaustive]
+pub struct TypeMask;
+
+#[allow(dead_code)]
+impl TypeMask {
+    pub const OBJECT: u32         = 0x0001;
+    pub const ITEM: u32           = 0x0002;
+    pub const CONTAINER: u32      = 0x0004;
+    pub const UNIT: u32           = 0x0008;
+    pub const PLAYER: u32         = 0x0010;
+    pub const GAME_OBJECT: u32    = 0x0020;
+    pub const DYNAMIC_OBJECT: u32 = 0x0040;
+    pub const CORPSE: u32         = 0x0080;
+}
+
+#[non_exhaustive]
+pub struct UpdateType;
+
+#[allow(dead_code)]
+impl UpdateType {
+    pub const VALUES: u8               = 0;
+    pub const MOVEMENT: u8             = 1;
+    pub const CREATE_OBJECT: u8        = 2;
+    pub const CREATE_OBJECT2: u8       = 3;
+    pub const OUT_OF_RANGE_OBJECTS: u8 = 4;
+    pub const NEAR_OBJECTS: u8         = 5;
+}
+
+// describe which parts the movement block of the created object contains
+#[non_exhaustive]
+pub struct UpdateFlags;
+
+#[allow(dead_code)]
+impl UpdateFlags {
+    pub const NONE: u16                = 0x0000;
+    pub const SELF: u16                = 0x0001;
+    pub const TRANSPORT: u16           = 0x0002;
+    pub const HAS_TARGET: u16          = 0x0004;
+    pub const UNKNOWN: u16             = 0x0008;
+    pub const LOW_GUID: u16            = 0x0010;
+    pub const LIVING: u16              = 0x0020;
+    pub const STATIONARY_POSITION: u16 = 0x0040;
+    pub const VEHICLE: u16             = 0x0080;
+    pub const POSITION: u16            = 0x0100;
+    pub const ROTATION: u16            = 0x0200;
+}
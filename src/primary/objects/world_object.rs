@@ -0,0 +1,162 @@
+use std::collections::BTreeMap;
+
+use crate::primary::objects::fields::{ObjectFields, PlayerFields, UnitFields};
+use crate::primary::objects::types::{ObjectType, TypeMask};
+use crate::primary::types::fields::update_blocks::{UpdateMask, UpdateValues};
+
+// update fields of the object, changed ones are sent with the next values update
+#[derive(Clone, Debug)]
+pub struct WorldObject {
+    pub guid: u64,
+    pub object_type: u8,
+    values: Vec<u32>,
+    changed: UpdateMask,
+}
+
+impl WorldObject {
+    pub fn new(guid: u64, object_type: u8, type_mask: u32, fields_count: u16) -> Self {
+        let mut object = Self {
+            guid,
+            object_type,
+            values: vec![0; fields_count as usize],
+            changed: UpdateMask::new(fields_count),
+        };
+
+        object.set_u64(ObjectFields::GUID, guid);
+        object.set_u32(ObjectFields::TYPE, type_mask);
+        object.set_f32(ObjectFields::SCALE_X, 1.0);
+
+        object
+    }
+
+    pub fn unit(guid: u64) -> Self {
+        Self::new(guid, ObjectType::UNIT, TypeMask::OBJECT | TypeMask::UNIT, UnitFields::END)
+    }
+
+    pub fn player(guid: u64) -> Self {
+        Self::new(
+            guid,
+            ObjectType::PLAYER,
+            TypeMask::OBJECT | TypeMask::UNIT | TypeMask::PLAYER,
+            PlayerFields::END,
+        )
+    }
+
+    pub fn fields_count(&self) -> u16 {
+        self.values.len() as u16
+    }
+
+    pub fn get_u32(&self, index: u16) -> u32 {
+        self.values[index as usize]
+    }
+
+    pub fn get_f32(&self, index: u16) -> f32 {
+        f32::from_bits(self.get_u32(index))
+    }
+
+    pub fn get_u64(&self, index: u16) -> u64 {
+        ((self.get_u32(index + 1) as u64) << 32) | self.get_u32(index) as u64
+    }
+
+    pub fn get_byte(&self, index: u16, offset: u8) -> u8 {
+        self.get_u32(index).to_le_bytes()[offset as usize]
+    }
+
+    pub fn set_u32(&mut self, index: u16, value: u32) {
+        if self.values[index as usize] != value {
+            self.values[index as usize] = value;
+            self.changed.set(index);
+        }
+    }
+
+    pub fn set_f32(&mut self, index: u16, value: f32) {
+        self.set_u32(index, value.to_bits());
+    }
+
+    // 64-bit values take two fields, low part first
+    pub fn set_u64(&mut self, index: u16, value: u64) {
+        self.set_u32(index, value as u32);
+        self.set_u32(index + 1, (value >> 32) as u32);
+    }
+
+    pub fn set_byte(&mut self, index: u16, offset: u8, value: u8) {
+        let mut bytes = self.get_u32(index).to_le_bytes();
+        bytes[offset as usize] = value;
+        self.set_u32(index, u32::from_le_bytes(bytes));
+    }
+
+    pub fn set_bytes(&mut self, index: u16, bytes: [u8; 4]) {
+        self.set_u32(index, u32::from_le_bytes(bytes));
+    }
+
+    pub fn has_changes(&self) -> bool {
+        !self.changed.is_empty()
+    }
+
+    // every non-zero field, the client keeps zero for the fields which are not sent on create
+    pub fn values(&self) -> UpdateValues {
+        let fields = self.values.iter()
+            .enumerate()
+            .filter(|(_, value)| **value != 0)
+            .map(|(index, value)| (index as u16, *value))
+            .collect::<BTreeMap<u16, u32>>();
+
+        UpdateValues { mask_blocks: self.mask_blocks(), fields }
+    }
+
+    // fields changed since the last call
+    pub fn changes(&mut self) -> UpdateValues {
+        let fields = self.changed.indexes()
+            .map(|index| (index, self.values[index as usize]))
+            .collect::<BTreeMap<u16, u32>>();
+        self.changed.clear();
+
+        UpdateValues { mask_blocks: self.mask_blocks(), fields }
+    }
+
+    fn mask_blocks(&self) -> u8 {
+        self.changed.blocks().len() as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::primary::objects::fields::{ObjectFields, UnitFields};
+    use crate::primary::objects::WorldObject;
+
+    #[test]
+    fn test_player_values() {
+        let mut player = WorldObject::player(0x0100000000000002);
+        player.set_byte(UnitFields::BYTES_0, 1, 7);
+
+        assert_eq!(player.get_u64(ObjectFields::GUID), 0x0100000000000002);
+        assert_eq!(player.get_byte(UnitFields::BYTES_0, 1), 7);
+
+        let values = player.values();
+        assert_eq!(values.mask_blocks, 42);
+        assert_eq!(values.fields, BTreeMap::from([
+            (0, 2),
+            (1, 0x01000000),
+            (2, 0x19),
+            (4, 1.0f32.to_bits()),
+            (UnitFields::BYTES_0, 0x0700),
+        ]));
+    }
+
+    #[test]
+    fn test_changes() {
+        let mut unit = WorldObject::unit(1);
+        unit.changes();
+        assert!(!unit.has_changes());
+
+        unit.set_u32(UnitFields::HEALTH, 100);
+        unit.set_u32(UnitFields::LEVEL, 0);
+        assert_eq!(unit.changes().fields, BTreeMap::from([(UnitFields::HEALTH, 100)]));
+
+        // same value is not sent again
+        unit.set_u32(UnitFields::HEALTH, 100);
+        assert!(!unit.has_changes());
+    }
+}
@@ -0,0 +1,206 @@
+use std::time::Duration;
+use anyhow::{bail, Result as AnyResult};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::timeout;
+
+use crate::primary::crypto::header_crypt::HeaderCrypt;
+use crate::primary::server::Opcode;
+
+// opcode + unknown + code
+const LOGIN_CHALLENGE_ERROR_SIZE: usize = 3;
+const LOGIN_CHALLENGE_SIZE: usize = 119;
+// opcode + error + 2 bytes flags
+const LOGIN_PROOF_ERROR_SIZE: usize = 4;
+const LOGIN_PROOF_SIZE: usize = 32;
+// opcode + code
+const RECONNECT_CHALLENGE_ERROR_SIZE: usize = 2;
+// opcode + code + reconnect proof + version challenge
+const RECONNECT_CHALLENGE_SIZE: usize = 34;
+const RECONNECT_PROOF_SIZE: usize = 4;
+// opcode + 2 bytes size of the rest of the packet
+const REALM_LIST_HEADER_SIZE: usize = 3;
+
+const SERVER_HEADER_SIZE: usize = 4;
+const SERVER_OPCODE_SIZE: usize = 2;
+// plain SMSG_AUTH_RESPONSE with the error code only, the server does not enable encryption then
+const AUTH_ERROR_HEADER: [u8; 4] = [0, 3, 0xEE, 0x01];
+
+// client side of the connection to the login or world server
+pub struct Connection {
+    stream: TcpStream,
+    login: bool,
+    buffer: Vec<u8>,
+    header_crypt: Option<HeaderCrypt>,
+    // encryption waits for the SMSG_AUTH_RESPONSE header, which is plain when the auth failed
+    pending_crypt: Option<HeaderCrypt>,
+    // header is decrypted only once, so its parts should be kept until the rest is received
+    first_byte: Option<u8>,
+    header: Option<(usize, u16)>,
+}
+
+impl Connection {
+    pub async fn connect(host: &str, port: u16, login: bool) -> AnyResult<Self> {
+        let stream = TcpStream::connect(format!("{}:{}", host, port)).await?;
+
+        Ok(Self {
+            stream,
+            login,
+            buffer: Vec::new(),
+            header_crypt: None,
+            pending_crypt: None,
+            first_byte: None,
+            header: None,
+        })
+    }
+
+    pub fn enable_header_crypt(&mut self, session_key: &[u8]) {
+        self.pending_crypt = Some(HeaderCrypt::client(session_key));
+    }
+
+    // login packets start with the opcode, world packets with the client header
+    pub async fn send(&mut self, mut packet: Vec<u8>) -> AnyResult<()> {
+        if let Some(header_crypt) = self.header_crypt.as_mut() {
+            header_crypt.encrypt(&mut packet[..6]);
+        }
+
+        self.stream.write_all(&packet).await?;
+
+        Ok(())
+    }
+
+    // returns None when the server sent nothing in time or closed the connection
+    pub async fn receive(&mut self, wait: Duration) -> AnyResult<Option<(u16, Vec<u8>)>> {
+        loop {
+            if let Some(packet) = self.decode()? {
+                return Ok(Some(packet));
+            }
+
+            let mut buf = [0; 65536];
+            match timeout(wait, self.stream.read(&mut buf)).await {
+                Ok(Ok(0)) | Err(_) => return Ok(None),
+                Ok(Ok(n)) => self.buffer.extend(&buf[..n]),
+                Ok(Err(err)) => return Err(err.into()),
+            }
+        }
+    }
+
+    fn decode(&mut self) -> AnyResult<Option<(u16, Vec<u8>)>> {
+        match self.login {
+            true => self.decode_login(),
+            false => self.decode_world(),
+        }
+    }
+
+    fn decode_login(&mut self) -> AnyResult<Option<(u16, Vec<u8>)>> {
+        if self.buffer.len() < 2 {
+            return Ok(None);
+        }
+
+        let opcode = self.buffer[0];
+        let size = match opcode {
+            Opcode::LOGIN_CHALLENGE => {
+                if self.buffer.len() < LOGIN_CHALLENGE_ERROR_SIZE {
+                    return Ok(None);
+                }
+
+                match self.buffer[2] {
+                    0 => LOGIN_CHALLENGE_SIZE,
+                    _ => LOGIN_CHALLENGE_ERROR_SIZE,
+                }
+            },
+            Opcode::LOGIN_PROOF => match self.buffer[1] {
+                0 => LOGIN_PROOF_SIZE,
+                _ => LOGIN_PROOF_ERROR_SIZE,
+            },
+            Opcode::RECONNECT_CHALLENGE => match self.buffer[1] {
+                0 => RECONNECT_CHALLENGE_SIZE,
+                _ => RECONNECT_CHALLENGE_ERROR_SIZE,
+            },
+            Opcode::RECONNECT_PROOF => RECONNECT_PROOF_SIZE,
+            Opcode::REALM_LIST => {
+                if self.buffer.len() < REALM_LIST_HEADER_SIZE {
+                    return Ok(None);
+                }
+
+                REALM_LIST_HEADER_SIZE + u16::from_le_bytes([self.buffer[1], self.buffer[2]]) as usize
+            },
+            _ => bail!("Unknown login opcode: {}", opcode),
+        };
+
+        if self.buffer.len() < size {
+            return Ok(None);
+        }
+
+        let packet = self.buffer.drain(..size).collect::<Vec<u8>>();
+
+        Ok(Some((opcode as u16, packet[1..].to_vec())))
+    }
+
+    fn decode_world(&mut self) -> AnyResult<Option<(u16, Vec<u8>)>> {
+        if self.header.is_none() {
+            if self.buffer.len() < SERVER_HEADER_SIZE {
+                return Ok(None);
+            }
+
+            if let Some(header_crypt) = self.pending_crypt.take() {
+                if self.buffer[..SERVER_HEADER_SIZE] != AUTH_ERROR_HEADER {
+                    self.header_crypt = Some(header_crypt);
+                }
+            }
+
+            // the first byte tells if the size takes 2 or 3 bytes
+            let first = match self.first_byte {
+                Some(first) => first,
+                None => {
+                    let mut first = [self.buffer[0]];
+                    if let Some(header_crypt) = self.header_crypt.as_mut() {
+                        header_crypt.decrypt(&mut first);
+                    }
+                    self.first_byte = Some(first[0]);
+                    first[0]
+                },
+            };
+
+            let header_size = match first & 0x80 {
+                0 => SERVER_HEADER_SIZE,
+                _ => SERVER_HEADER_SIZE + 1,
+            };
+            if self.buffer.len() < header_size {
+                return Ok(None);
+            }
+
+            self.first_byte = None;
+            let mut header = self.buffer.drain(..header_size).collect::<Vec<u8>>();
+            header[0] = first;
+            if let Some(header_crypt) = self.header_crypt.as_mut() {
+                header_crypt.decrypt(&mut header[1..]);
+            }
+
+            let (size, opcode) = match header_size {
+                SERVER_HEADER_SIZE => (
+                    u16::from_be_bytes([header[0], header[1]]) as usize,
+                    u16::from_le_bytes([header[2], header[3]]),
+                ),
+                _ => (
+                    ((header[0] as usize & 0x7F) << 16) | ((header[1] as usize) << 8) | header[2] as usize,
+                    u16::from_le_bytes([header[3], header[4]]),
+                ),
+            };
+            if size < SERVER_OPCODE_SIZE {
+                bail!("Invalid packet size: {}", size);
+            }
+
+            self.header = Some((size - SERVER_OPCODE_SIZE, opcode));
+        }
+
+        let (body_size, opcode) = self.header.unwrap();
+        if self.buffer.len() < body_size {
+            return Ok(None);
+        }
+
+        self.header = None;
+
+        Ok(Some((opcode, self.buffer.drain(..body_size).collect())))
+    }
+}
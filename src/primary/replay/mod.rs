@@ -0,0 +1,320 @@
+mod connection;
+mod packets;
+mod report;
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::time::Duration;
+use anyhow::{Context, Result as AnyResult};
+use num_bigint::{BigInt, Sign};
+use sha1::{Digest, Sha1};
+
+use crate::primary::capture::{CaptureRecord, Direction, ServerKind};
+use crate::primary::crypto::srp::Srp;
+use crate::primary::replay::connection::Connection;
+use crate::primary::replay::packets::{
+    AuthChallenge, AuthSession, ClientChallenge, LoginChallenge, LoginProof, ReconnectChallenge, ReconnectProof,
+};
+use crate::primary::server::{LoginServer, Opcode, WorldServer};
+use crate::primary::traits::server::Server;
+pub use report::{OpcodeReport, Report};
+
+#[derive(Debug, Clone)]
+pub struct ReplayOptions {
+    pub host: String,
+    // passwords by uppercased account name, without them recorded proofs are sent as is
+    pub passwords: HashMap<String, String>,
+    // time to wait for every recorded response
+    pub timeout: Duration,
+}
+
+// replays inbound packets of the capture and compares the responses with outbound ones
+pub struct Replay {
+    options: ReplayOptions,
+    clients: HashMap<u64, Client>,
+    // session keys of accounts logged in during the replay
+    session_keys: HashMap<String, Vec<u8>>,
+    report: Report,
+}
+
+impl Replay {
+    pub fn new(options: ReplayOptions) -> Self {
+        Self {
+            options,
+            clients: HashMap::new(),
+            session_keys: HashMap::new(),
+            report: Report::default(),
+        }
+    }
+
+    pub async fn run(mut self, records: Vec<CaptureRecord>) -> AnyResult<Report> {
+        // connection is closed after its last record like the recorded client did
+        let last_records: HashMap<u64, usize> = records.iter()
+            .enumerate()
+            .map(|(index, record)| (record.connection_id, index))
+            .collect();
+
+        // recorded response of every packet tells if its proof was accepted and should be signed again
+        let mut responses = vec![None; records.len()];
+        let mut next_response: HashMap<u64, usize> = HashMap::new();
+        for (index, record) in records.iter().enumerate().rev() {
+            match record.direction {
+                Direction::Inbound => responses[index] = next_response.get(&record.connection_id).copied(),
+                Direction::Outbound => {
+                    next_response.insert(record.connection_id, index);
+                },
+            }
+        }
+
+        for (index, record) in records.iter().enumerate() {
+            if !self.clients.contains_key(&record.connection_id) {
+                let login = record.server == ServerKind::Login;
+                let connection = Connection::connect(&self.options.host, record.port, login).await
+                    .with_context(|| format!("Cannot connect to {}:{}", self.options.host, record.port))?;
+
+                self.clients.insert(record.connection_id, Client::new(connection, login));
+            }
+
+            match record.direction {
+                Direction::Inbound => self.send(record, responses[index].map(|index| &records[index])).await,
+                Direction::Outbound => self.receive(record).await,
+            }
+
+            if last_records[&record.connection_id] == index {
+                self.clients.remove(&record.connection_id);
+            }
+        }
+
+        Ok(self.report)
+    }
+
+    async fn send(&mut self, record: &CaptureRecord, response: Option<&CaptureRecord>) {
+        let client = self.clients.get_mut(&record.connection_id).unwrap();
+        let Some(mut connection) = client.connection.take() else {
+            return;
+        };
+
+        let result = match client.packet(record, response, &self.options, &mut self.session_keys) {
+            Ok((packet, session_key)) => {
+                if let Some(session_key) = session_key {
+                    connection.enable_header_crypt(&session_key);
+                }
+                connection.send(packet).await
+            },
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(()) => client.connection = Some(connection),
+            Err(err) => {
+                let opcode_name = opcode_name(client.login, record.opcode);
+                self.report.different(&opcode_name, format!("#{} cannot be sent: {}", record.connection_id, err));
+            },
+        }
+    }
+
+    async fn receive(&mut self, record: &CaptureRecord) {
+        let client = self.clients.get_mut(&record.connection_id).unwrap();
+        let expected_name = opcode_name(client.login, record.opcode);
+
+        let received = match client.connection.as_mut() {
+            Some(connection) => connection.receive(self.options.timeout).await,
+            None => Ok(None),
+        };
+
+        let difference = match received {
+            Ok(Some((opcode, body))) => {
+                client.update(opcode, &body);
+
+                if opcode != record.opcode {
+                    Some(format!("{} received instead", opcode_name(client.login, opcode)))
+                } else {
+                    Report::compare(&record.body, &body, random_range(client.login, opcode))
+                }
+            },
+            Ok(None) => Some("no response".to_string()),
+            Err(err) => {
+                client.connection = None;
+                Some(format!("cannot be received: {}", err))
+            },
+        };
+
+        match difference {
+            Some(difference) => {
+                self.report.different(&expected_name, format!("#{} {}", record.connection_id, difference));
+            },
+            None => self.report.matched(&expected_name),
+        }
+    }
+}
+
+// state of one recorded connection needed to sign its packets again
+struct Client {
+    // closed when the packet cannot be sent or received
+    connection: Option<Connection>,
+    login: bool,
+    account: Option<String>,
+    srp: Option<Srp>,
+    reconnect_proof: Option<[u8; 16]>,
+    server_seed: u32,
+}
+
+impl Client {
+    fn new(connection: Connection, login: bool) -> Self {
+        Self {
+            connection: Some(connection),
+            login,
+            account: None,
+            srp: None,
+            reconnect_proof: None,
+            server_seed: 0,
+        }
+    }
+
+    // builds the packet to send, returns session key when the world auth is signed with it
+    fn packet(
+        &mut self,
+        record: &CaptureRecord,
+        response: Option<&CaptureRecord>,
+        options: &ReplayOptions,
+        session_keys: &mut HashMap<String, Vec<u8>>,
+    ) -> AnyResult<(Vec<u8>, Option<Vec<u8>>)> {
+        // failed login proofs have non-zero error, failed world auth has the code only
+        let accepted = match (self.login, response) {
+            (true, Some(response)) => response.body.first() == Some(&0),
+            (false, Some(response)) => response.body.len() > 1,
+            (_, None) => false,
+        };
+
+        if !self.login {
+            if record.opcode as u32 == Opcode::CMSG_AUTH_SESSION && accepted {
+                let (mut auth_session, _) = AuthSession::from_binary(&record.body)?;
+                let account = auth_session.account.0.to_uppercase();
+
+                if let Some(session_key) = session_keys.get(&account) {
+                    auth_session.digest = Sha1::new()
+                        .chain(account.as_bytes())
+                        .chain([0u8; 4])
+                        .chain(auth_session.client_seed.to_le_bytes())
+                        .chain(self.server_seed.to_le_bytes())
+                        .chain(session_key)
+                        .finalize()
+                        .into();
+
+                    return Ok((auth_session.to_binary()?, Some(session_key.clone())));
+                }
+            }
+
+            let header = [
+                ((record.body.len() + 4) as u16).to_be_bytes().to_vec(),
+                (record.opcode as u32).to_le_bytes().to_vec(),
+            ].concat();
+
+            return Ok(([header, record.body.clone()].concat(), None));
+        }
+
+        let opcode = record.opcode as u8;
+        match opcode {
+            Opcode::LOGIN_CHALLENGE | Opcode::RECONNECT_CHALLENGE => {
+                let (challenge, _) = ClientChallenge::from_binary(&record.body)?;
+                self.account = Some(challenge.account.to_uppercase());
+            },
+            Opcode::LOGIN_PROOF if accepted => {
+                let account = self.account.clone().unwrap_or_default();
+                if let (Some(mut srp), Some(password)) = (self.srp.take(), options.passwords.get(&account)) {
+                    let (mut proof, _) = LoginProof::from_binary(&record.body)?;
+
+                    // little-endian number is padded with zeroes up to the field size
+                    let mut client_ephemeral = [0u8; 32];
+                    let ephemeral = srp.client_ephemeral();
+                    client_ephemeral[..ephemeral.len()].copy_from_slice(&ephemeral);
+
+                    srp.calculate_client_session_key::<Sha1>(password, &client_ephemeral);
+                    proof.client_ephemeral = client_ephemeral;
+                    proof.client_proof.copy_from_slice(&srp.calculate_proof::<Sha1>(&client_ephemeral));
+                    session_keys.insert(account, srp.session_key.unwrap());
+
+                    return Ok((proof.to_binary()?, None));
+                }
+            },
+            Opcode::RECONNECT_PROOF if accepted => {
+                let account = self.account.clone().unwrap_or_default();
+                if let (Some(reconnect_proof), Some(session_key)) = (self.reconnect_proof.take(), session_keys.get(&account)) {
+                    let (mut proof, _) = ReconnectProof::from_binary(&record.body)?;
+
+                    proof.client_proof = Sha1::new()
+                        .chain(account.as_bytes())
+                        .chain(proof.proof_data)
+                        .chain(reconnect_proof)
+                        .chain(session_key)
+                        .finalize()
+                        .into();
+
+                    return Ok((proof.to_binary()?, None));
+                }
+            },
+            _ => {},
+        }
+
+        Ok(([vec![opcode], record.body.clone()].concat(), None))
+    }
+
+    // keeps random values of the server which the next packets are signed with
+    fn update(&mut self, opcode: u16, body: &[u8]) {
+        if !self.login {
+            if opcode == Opcode::SMSG_AUTH_CHALLENGE {
+                if let Ok((challenge, _)) = AuthChallenge::from_binary(body) {
+                    self.server_seed = challenge.server_seed;
+                }
+            }
+
+            return;
+        }
+
+        match opcode as u8 {
+            Opcode::LOGIN_CHALLENGE => {
+                if let Ok((challenge, _)) = LoginChallenge::from_binary(body) {
+                    if challenge.code == 0 {
+                        let mut srp = Srp::new();
+                        srp.set_account(self.account.clone().unwrap_or_default());
+                        srp.set_salt(challenge.salt);
+                        srp.generator = BigInt::from(challenge.g);
+                        srp.modulus = BigInt::from_bytes_le(Sign::Plus, &challenge.n);
+                        srp.set_server_ephemeral(&challenge.server_ephemeral);
+                        self.srp = Some(srp);
+                    }
+                }
+            },
+            Opcode::RECONNECT_CHALLENGE => {
+                if let Ok((challenge, _)) = ReconnectChallenge::from_binary(body) {
+                    if challenge.code == 0 {
+                        self.reconnect_proof = Some(challenge.reconnect_proof);
+                    }
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+fn opcode_name(login: bool, opcode: u16) -> String {
+    match login {
+        true => LoginServer::opcode_name(opcode),
+        false => WorldServer::opcode_name(opcode),
+    }
+}
+
+// part of the server packet which is random on every connection
+fn random_range(login: bool, opcode: u16) -> Option<Range<usize>> {
+    match (login, opcode) {
+        // server ephemeral
+        (true, opcode) if opcode == Opcode::LOGIN_CHALLENGE as u16 => Some(2..34),
+        // server proof
+        (true, opcode) if opcode == Opcode::LOGIN_PROOF as u16 => Some(1..21),
+        // reconnect proof
+        (true, opcode) if opcode == Opcode::RECONNECT_CHALLENGE as u16 => Some(1..17),
+        // server seed and seeds
+        (false, opcode) if opcode == Opcode::SMSG_AUTH_CHALLENGE => Some(4..40),
+        _ => None,
+    }
+}
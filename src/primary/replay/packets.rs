@@ -0,0 +1,112 @@
+use std::io::BufRead;
+use serde::{Deserialize, Serialize};
+use tentacli::packet::custom_fields::TerminatedString;
+use tentacli::packet::idewave::{LoginPacket, WorldPacket};
+
+use crate::primary::server::Opcode;
+use crate::with_opcode;
+
+// packets are described from the client side: the ones the replay reads from the server
+// and the ones it signs again with the keys of the current connection
+
+with_opcode! {
+    @login_opcode(Opcode::LOGIN_CHALLENGE)
+    #[derive(LoginPacket, Serialize, Deserialize, Debug)]
+    pub struct ClientChallenge {
+        unknown: u8,
+        packet_size: u16,
+        game_name: TerminatedString,
+        version: [u8; 3],
+        build: u16,
+        platform: TerminatedString,
+        os: TerminatedString,
+        locale: [u8; 4],
+        timezone: u32,
+        ip: [u8; 4],
+        account_length: u8,
+        pub account: String,
+    }
+}
+
+with_opcode! {
+    @login_opcode(Opcode::LOGIN_CHALLENGE)
+    #[derive(LoginPacket, Serialize, Deserialize, Debug)]
+    pub struct LoginChallenge {
+        unknown: u8,
+        pub code: u8,
+        pub server_ephemeral: [u8; 32],
+        g_len: u8,
+        pub g: u8,
+        n_len: u8,
+        pub n: [u8; 32],
+        pub salt: [u8; 32],
+    }
+}
+
+with_opcode! {
+    @login_opcode(Opcode::LOGIN_PROOF)
+    #[derive(LoginPacket, Serialize, Deserialize, Debug)]
+    pub struct LoginProof {
+        pub client_ephemeral: [u8; 32],
+        pub client_proof: [u8; 20],
+        crc_hash: [u8; 20],
+        keys_count: u8,
+        security_flags: u8,
+    }
+}
+
+with_opcode! {
+    @login_opcode(Opcode::RECONNECT_CHALLENGE)
+    #[derive(LoginPacket, Serialize, Deserialize, Debug)]
+    pub struct ReconnectChallenge {
+        pub code: u8,
+        pub reconnect_proof: [u8; 16],
+    }
+}
+
+with_opcode! {
+    @login_opcode(Opcode::RECONNECT_PROOF)
+    #[derive(LoginPacket, Serialize, Deserialize, Debug)]
+    pub struct ReconnectProof {
+        pub proof_data: [u8; 16],
+        pub client_proof: [u8; 20],
+        client_checksum: [u8; 20],
+        keys_count: u8,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_AUTH_CHALLENGE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    pub struct AuthChallenge {
+        unknown: u32,
+        pub server_seed: u32,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::CMSG_AUTH_SESSION)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    pub struct AuthSession {
+        build: u32,
+        login_server_id: u32,
+        pub account: TerminatedString,
+        login_server_type: u32,
+        pub client_seed: u32,
+        region_id: u32,
+        battlegroup_id: u32,
+        realm_id: u32,
+        dos_response: u64,
+        pub digest: [u8; 20],
+        #[dynamic_field]
+        addon_info: Vec<u8>,
+    }
+
+    impl AuthSession {
+        fn addon_info<R: BufRead>(mut reader: R, _: &mut Self) -> Vec<u8> {
+            let mut buffer = Vec::new();
+            reader.read_to_end(&mut buffer).unwrap();
+            buffer
+        }
+    }
+}
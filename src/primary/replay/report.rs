@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+#[derive(Debug, Default)]
+pub struct OpcodeReport {
+    pub matched: usize,
+    pub differences: Vec<String>,
+}
+
+// results of the comparison of server responses grouped by the recorded opcode name
+#[derive(Debug, Default)]
+pub struct Report {
+    pub opcodes: BTreeMap<String, OpcodeReport>,
+}
+
+impl Report {
+    pub fn matched(&mut self, opcode_name: &str) {
+        self.opcodes.entry(opcode_name.to_string()).or_default().matched += 1;
+    }
+
+    pub fn different(&mut self, opcode_name: &str, difference: String) {
+        self.opcodes.entry(opcode_name.to_string()).or_default().differences.push(difference);
+    }
+
+    pub fn has_differences(&self) -> bool {
+        self.opcodes.values().any(|report| !report.differences.is_empty())
+    }
+
+    // describes the first difference, bytes in the ignored range are random on every connection
+    pub fn compare(expected: &[u8], received: &[u8], ignored: Option<Range<usize>>) -> Option<String> {
+        if expected.len() != received.len() {
+            return Some(format!("{} bytes instead of {}", received.len(), expected.len()));
+        }
+
+        expected.iter().zip(received).enumerate()
+            .find(|(index, (expected, received))| {
+                expected != received && !ignored.as_ref().is_some_and(|range| range.contains(index))
+            })
+            .map(|(index, (expected, received))| {
+                format!("byte {}: {:02X} instead of {:02X}", index, received, expected)
+            })
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (opcode_name, report) in &self.opcodes {
+            writeln!(f, "{}: {} matched, {} different", opcode_name, report.matched, report.differences.len())?;
+            for difference in &report.differences {
+                writeln!(f, "  {}", difference)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primary::replay::Report;
+
+    #[test]
+    fn test_compare() {
+        assert_eq!(Report::compare(&[0, 1, 2], &[0, 1, 2], None), None);
+        assert_eq!(Report::compare(&[0, 1, 2], &[0, 1], None), Some("2 bytes instead of 3".to_string()));
+        assert_eq!(Report::compare(&[0, 1, 2], &[0, 5, 3], None), Some("byte 1: 05 instead of 01".to_string()));
+        assert_eq!(Report::compare(&[0, 1, 2], &[0, 5, 3], Some(1..2)), Some("byte 2: 03 instead of 02".to_string()));
+        assert_eq!(Report::compare(&[0, 1, 2], &[0, 5, 3], Some(1..3)), None);
+    }
+
+    #[test]
+    fn test_report_output() {
+        let mut report = Report::default();
+        report.matched("REALM_LIST");
+        report.different("LOGIN_PROOF", "#1 4 bytes instead of 32".to_string());
+
+        assert!(report.has_differences());
+        assert_eq!(
+            report.to_string(),
+            "LOGIN_PROOF: 0 matched, 1 different\n  #1 4 bytes instead of 32\nREALM_LIST: 1 matched, 0 different\n",
+        );
+    }
+}
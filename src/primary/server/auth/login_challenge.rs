@@ -2,15 +2,20 @@ use std::io::BufRead;
 use async_trait::async_trait;
 use tentacli::packet::custom_fields::TerminatedString;
 use serde::{Deserialize, Serialize};
-use sha1::Sha1;
 use tentacli::packet::idewave::LoginPacket;
 
 use crate::with_opcode;
+use crate::primary::crypto::srp::Srp;
+use crate::primary::server::auth::types::AuthResult;
 use crate::primary::server::opcodes::Opcode;
 use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::traits::server::RunOptions;
 use crate::primary::types::{HandlerInput, HandlerOutput, HandlerResult};
 
-const VERSION_CHALLENGE: [u8; 16] = [
+// fixed fields and terminators of the empty strings before the account name
+pub(super) const MIN_INCOME_SIZE: usize = 24;
+
+pub(super) const VERSION_CHALLENGE: [u8; 16] = [
     0xBA, 0xA3, 0x1E, 0x99, 0xA0, 0x0B, 0x21, 0x57,
     0xFC, 0x37, 0x3F, 0xB3, 0x69, 0xCD, 0xD2, 0xF1
 ];
@@ -18,28 +23,52 @@ const VERSION_CHALLENGE: [u8; 16] = [
 with_opcode! {
     @login_opcode(Opcode::LOGIN_CHALLENGE)
     #[derive(LoginPacket, Serialize, Deserialize, Debug)]
-    struct Income {
+    pub(super) struct Income {
         unknown: u8,
         packet_size: u16,
         game_name: TerminatedString,
         version: [u8; 3],
-        build: u16,
-        platform: TerminatedString,
-        os: TerminatedString,
+        pub(super) build: u16,
+        #[dynamic_field]
+        pub(super) platform: String,
+        #[dynamic_field]
+        os: String,
         #[dynamic_field]
         locale: String,
         timezone: u32,
         ip: [u8; 4],
         account_length: u8,
-        account: String,
+        pub(super) account: String,
     }
 
     impl Income {
+        fn platform<R: BufRead>(reader: R, _: &mut Self) -> String {
+            Self::read_reversed_string(reader)
+        }
+
+        fn os<R: BufRead>(reader: R, _: &mut Self) -> String {
+            Self::read_reversed_string(reader)
+        }
+
+        // client sends platform and os as reversed null-terminated strings ("68x" for "x86")
+        fn read_reversed_string<R: BufRead>(mut reader: R) -> String {
+            let mut buffer = Vec::new();
+            reader.read_until(0, &mut buffer).unwrap_or_default();
+            if buffer.last() == Some(&0) {
+                buffer.pop();
+            }
+            buffer.reverse();
+            String::from_utf8_lossy(&buffer).into_owned()
+        }
+
+        // truncated locale is left empty, the next fields fail to read then
         fn locale<R: BufRead>(mut reader: R, _: &mut Self) -> String {
             let mut buffer = vec![0u8; 4];
-            reader.read_exact(&mut buffer).unwrap();
+            if reader.read_exact(&mut buffer).is_err() {
+                return String::new();
+            }
             buffer.reverse();
-            String::from_utf8(buffer).unwrap()
+            String::from_utf8_lossy(&buffer).into_owned()
         }
     }
 }
@@ -62,17 +91,57 @@ with_opcode! {
     }
 }
 
+with_opcode! {
+    @login_opcode(Opcode::LOGIN_CHALLENGE)
+    #[derive(LoginPacket, Serialize, Deserialize, Debug)]
+    struct ErrorOutcome {
+        unknown: u8,
+        code: u8,
+    }
+}
+
 pub struct Handler;
 #[async_trait]
 impl PacketHandler for Handler {
     async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
         let mut response = Vec::new();
 
-        let (Income { account, .. }, _) = Income::from_binary(&input.data)?;
-        let mut srp = input.srp.lock().unwrap();
-        srp.set_account(account);
-        srp.generate_verifier::<Sha1>();
-        srp.generate_server_ephemeral::<Sha1>();
+        // the client waits for the answer forever, so truncated challenge is rejected too
+        let income = match input.data.len() < MIN_INCOME_SIZE {
+            true => None,
+            false => Income::from_binary(&input.data).ok(),
+        };
+        let Some((Income { build, platform, account, .. }, json_details)) = income else {
+            return Self::reject(AuthResult::FAIL_VERSION_INVALID);
+        };
+        input.json_details = Some(json_details);
+
+        let options = &input.options;
+        if !Self::is_client_allowed(options, build, &platform) {
+            return Self::reject(AuthResult::FAIL_VERSION_INVALID);
+        }
+
+        let account = match options.accounts.get(&account) {
+            Some(account) if account.banned => {
+                return Self::reject(AuthResult::FAIL_BANNED);
+            },
+            Some(account) if account.suspended => {
+                return Self::reject(AuthResult::FAIL_SUSPENDED);
+            },
+            Some(account) if options.realm_status.is_online(&account.name) => {
+                return Self::reject(AuthResult::FAIL_ALREADY_ONLINE);
+            },
+            Some(account) => account,
+            None => {
+                return Self::reject(AuthResult::FAIL_UNKNOWN_ACCOUNT);
+            },
+        };
+
+        let mut srp = Srp::new();
+        srp.set_account(account.name.clone());
+        srp.set_salt(account.salt);
+        srp.set_verifier(&account.verifier);
+        srp.generate_server_ephemeral();
 
         let (_, server_ephemeral) = srp.server_ephemeral.as_ref().unwrap().to_bytes_le();
         let (_, generator) = srp.generator.to_bytes_le();
@@ -80,7 +149,7 @@ impl PacketHandler for Handler {
 
         response.push(HandlerOutput::Data(Outcome {
             unknown: 0,
-            code: 0,
+            code: AuthResult::SUCCESS,
             server_ephemeral,
             g_len: 1,
             g: generator,
@@ -89,8 +158,136 @@ impl PacketHandler for Handler {
             salt: srp.salt,
             version_challenge: VERSION_CHALLENGE,
             unknown2: 0,
-        }.to_binary()?));
+        }.unpack()?));
+
+        let mut session = input.session.lock().unwrap();
+        session.srp = Some(srp);
+        session.challenged_account = Some(account.name);
 
         Ok(response)
     }
-}
\ No newline at end of file
+}
+
+impl Handler {
+    pub(super) fn is_client_allowed(options: &RunOptions, build: u16, platform: &str) -> bool {
+        options.config.login.allowed_builds.contains(&build)
+            && options.config.login.allowed_platforms.iter().any(|allowed| allowed == platform)
+    }
+
+    fn reject(code: u8) -> HandlerResult {
+        Ok(vec![HandlerOutput::Data(ErrorOutcome { unknown: 0, code }.unpack()?)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::primary::accounts::InMemoryAccountRepository;
+    use crate::primary::config::Config;
+    use crate::primary::server::auth::login_challenge::Handler;
+    use crate::primary::server::auth::types::AuthResult;
+    use crate::primary::server::Opcode;
+    use crate::primary::traits::server::{handle_single, test_input, RunOptions};
+    use crate::primary::types::{Account, Session};
+
+    fn build_income(account: &str, build: u16) -> Vec<u8> {
+        build_income_platform(account, build, b"68x\0")
+    }
+
+    fn build_income_platform(account: &str, build: u16, platform: &[u8]) -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend(b"WoW\0");
+        body.extend([3, 3, 5]);
+        body.extend(build.to_le_bytes());
+        body.extend(platform);
+        body.extend(b"niW\0");
+        body.extend(b"SUne");
+        body.extend(0u32.to_le_bytes());
+        body.extend([127, 0, 0, 1]);
+        body.push(account.len() as u8);
+        body.extend(account.as_bytes());
+
+        [vec![0], (body.len() as u16).to_le_bytes().to_vec(), body].concat()
+    }
+
+    async fn handle(accounts: InMemoryAccountRepository, account: &str) -> Vec<u8> {
+        handle_build(accounts, account, 12340).await
+    }
+
+    async fn handle_build(accounts: InMemoryAccountRepository, account: &str, build: u16) -> Vec<u8> {
+        handle_data(accounts, build_income(account, build)).await
+    }
+
+    async fn handle_data(accounts: InMemoryAccountRepository, data: Vec<u8>) -> Vec<u8> {
+        let options = Arc::new(RunOptions {
+            accounts: Arc::new(accounts),
+            ..RunOptions::for_tests(Config::default())
+        });
+
+        handle_single(&mut Handler, &mut test_input(&options, Session::new(), Opcode::LOGIN_CHALLENGE as u32, data)).await
+    }
+
+    #[tokio::test]
+    async fn test_unknown_account() {
+        let packet = handle(InMemoryAccountRepository::new(), "TEST").await;
+        assert_eq!(packet, vec![0, 0, AuthResult::FAIL_UNKNOWN_ACCOUNT]);
+    }
+
+    #[tokio::test]
+    async fn test_banned_account() {
+        let accounts = InMemoryAccountRepository::new();
+        accounts.insert(Account { banned: true, ..Account::new("test", "test") });
+
+        let packet = handle(accounts, "TEST").await;
+        assert_eq!(packet, vec![0, 0, AuthResult::FAIL_BANNED]);
+    }
+
+    #[tokio::test]
+    async fn test_already_online_account() {
+        let accounts = InMemoryAccountRepository::new();
+        accounts.insert(Account::new("test", "test"));
+        let options = Arc::new(RunOptions {
+            accounts: Arc::new(accounts),
+            ..RunOptions::for_tests(Config::default())
+        });
+        let _guard = options.realm_status.online(1).enter("TEST");
+
+        let mut input = test_input(&options, Session::new(), Opcode::LOGIN_CHALLENGE as u32, build_income("TEST", 12340));
+        let packet = handle_single(&mut Handler, &mut input).await;
+        assert_eq!(packet, vec![0, 0, AuthResult::FAIL_ALREADY_ONLINE]);
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_build() {
+        let accounts = InMemoryAccountRepository::new();
+        accounts.insert(Account::new("test", "test"));
+
+        let packet = handle_build(accounts, "TEST", 8606).await;
+        assert_eq!(packet, vec![0, 0, AuthResult::FAIL_VERSION_INVALID]);
+    }
+
+    #[tokio::test]
+    async fn test_known_account() {
+        let accounts = InMemoryAccountRepository::new();
+        accounts.insert(Account::new("test", "test"));
+
+        let packet = handle(accounts, "TEST").await;
+        assert_eq!(packet[..3], [0, 0, AuthResult::SUCCESS]);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_income() {
+        let accounts = InMemoryAccountRepository::new();
+        accounts.insert(Account::new("test", "test"));
+
+        // platform is not valid utf-8, so it is not allowed
+        let data = build_income_platform("TEST", 12340, b"\xFF\xFE\0");
+        let packet = handle_data(accounts, data).await;
+        assert_eq!(packet, vec![0, 0, AuthResult::FAIL_VERSION_INVALID]);
+
+        let data = build_income("TEST", 12340);
+        let packet = handle_data(InMemoryAccountRepository::new(), data[..20].to_vec()).await;
+        assert_eq!(packet, vec![0, 0, AuthResult::FAIL_VERSION_INVALID]);
+    }
+}
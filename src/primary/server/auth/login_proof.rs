@@ -1,9 +1,10 @@
+use anyhow::anyhow;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use sha1::{Digest, Sha1};
 use tentacli::packet::idewave::LoginPacket;
 
-use crate::primary::server::auth::types::AccountFlags;
+use crate::primary::server::auth::types::{AccountFlags, AuthResult};
 use crate::primary::server::opcodes::Opcode;
 
 use crate::primary::traits::packet_handler::PacketHandler;
@@ -34,14 +35,25 @@ with_opcode! {
     }
 }
 
+with_opcode! {
+    @login_opcode(Opcode::LOGIN_PROOF)
+    #[derive(LoginPacket, Serialize, Deserialize, Debug)]
+    struct ErrorOutcome {
+        error: u8,
+        login_flags: u16,
+    }
+}
+
 pub struct Handler;
 #[async_trait]
 impl PacketHandler for Handler {
     async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
         let mut response = Vec::new();
-        let (Income { client_ephemeral, client_proof, .. }, _) = Income::from_binary(&input.data)?;
+        let (Income { client_ephemeral, client_proof, .. }, json_details) = Income::from_binary(&input.data)?;
+        input.json_details = Some(json_details);
 
-        let mut srp = input.srp.lock().unwrap();
+        let mut srp = input.session.lock().unwrap().srp.take()
+            .ok_or_else(|| anyhow!("LOGIN_PROOF received before LOGIN_CHALLENGE"))?;
         srp.calculate_session_key::<Sha1>(&client_ephemeral);
 
         let server_proof = srp.calculate_proof::<Sha1>(&client_ephemeral);
@@ -55,7 +67,7 @@ impl PacketHandler for Handler {
                 let result = hasher
                     .chain(client_ephemeral)
                     .chain(server_proof)
-                    .chain(session_key.to_vec())
+                    .chain(&session_key)
                     .finalize();
 
                 let mut hashed_proof = [0u8; 20];
@@ -63,14 +75,24 @@ impl PacketHandler for Handler {
                 hashed_proof
             };
 
+            {
+                let mut session = input.session.lock().unwrap();
+                session.account = session.challenged_account.take();
+            }
+
             response.push(HandlerOutput::SessionKey(session_key));
             response.push(HandlerOutput::Data(Outcome {
-                error: 0,
+                error: AuthResult::SUCCESS,
                 server_proof,
                 account_flags: AccountFlags::ACCOUNT_FLAG_PROPASS,
                 survey_id: 0,
                 unknown_flags: 0,
-            }.to_binary()?));
+            }.unpack()?));
+        } else {
+            response.push(HandlerOutput::Data(ErrorOutcome {
+                error: AuthResult::FAIL_INCORRECT_PASSWORD,
+                login_flags: 0,
+            }.unpack()?));
         }
 
         Ok(response)
@@ -1,13 +1,13 @@
 mod login_challenge;
 mod realmlist;
 mod login_proof;
-mod types;
-mod auth_challenge;
+mod reconnect_challenge;
+mod reconnect_proof;
+pub(super) mod types;
 
 use crate::primary::server::opcodes::Opcode;
 use crate::primary::traits::processor::Processor;
 use crate::primary::types::{HandlerInput, ProcessorResult};
-pub use auth_challenge::handle as auth_challenge;
 
 pub struct AuthProcessor;
 
@@ -22,6 +22,12 @@ impl Processor for AuthProcessor {
             Opcode::LOGIN_PROOF => {
                 vec![Box::new(login_proof::Handler)]
             },
+            Opcode::RECONNECT_CHALLENGE if input.options.config.features.reconnect => {
+                vec![Box::new(reconnect_challenge::Handler)]
+            },
+            Opcode::RECONNECT_PROOF if input.options.config.features.reconnect => {
+                vec![Box::new(reconnect_proof::Handler)]
+            },
             Opcode::REALM_LIST => {
                 vec![Box::new(realmlist::Handler)]
             }
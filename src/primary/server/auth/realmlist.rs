@@ -1,24 +1,28 @@
+use anyhow::anyhow;
 use async_trait::async_trait;
-use rand::Rng;
 use serde::{Deserialize, Serialize};
-use tentacli::packet::idewave::{FieldsSerializer, LoginPacket};
-use tentacli::realm::Realm;
+use tentacli::packet::idewave::LoginPacket;
+use tentacli::traits::BinaryConverter;
 
+use crate::primary::config::RealmConfig;
+use crate::primary::server::auth::types::RealmFlags;
 use crate::primary::server::opcodes::Opcode;
-use crate::primary::server::WORLD_PORT;
 use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::traits::server::RunOptions;
 use crate::primary::types::{HandlerInput, HandlerOutput, HandlerResult};
-use crate::primary::types::fields::realms::Realms;
+use crate::primary::types::fields::realms::{Realm, Realms};
 use crate::with_opcode;
 
+// amount of accounts treated as high population when there is no player limit
+const DEFAULT_CAPACITY: usize = 100;
+
 with_opcode! {
     @login_opcode(Opcode::REALM_LIST)
     #[derive(LoginPacket, Serialize, Deserialize, Debug, Default)]
     struct Outcome {
         size: u16,
         unknown: u32,
-        realms_count: u16,
-        realms: Vec<u8>,
+        realms: Realms,
         unknown2: u16,
     }
 }
@@ -26,68 +30,198 @@ with_opcode! {
 pub struct Handler;
 #[async_trait]
 impl PacketHandler for Handler {
-    async fn handle(&mut self, _: &mut HandlerInput) -> HandlerResult {
+    async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
         let mut response = Vec::new();
-        let realms = Self::generate_unique_realms();
-        let realms_count = realms.len() as u16;
-
-        let realms_bytes = {
-            #[derive(FieldsSerializer, Serialize, Deserialize)]
-            struct RealmsSerializer {
-                realms: Realms,
-            }
+        // character counts are private, so the account must be proven first
+        let account = input.session.lock().unwrap().account.clone()
+            .ok_or_else(|| anyhow!("REALM_LIST received before authentication"))?;
+        let mut realms = Realms(Self::build_realms(&input.options, &account));
 
-            RealmsSerializer { realms: Realms(realms.clone()) }.to_binary().unwrap()
-        };
+        let mut realms_bytes = Vec::new();
+        realms.write_into(&mut realms_bytes)?;
 
         response.push(HandlerOutput::Data(Outcome {
-            size: (realms_bytes.len() + 8) as u16,
+            size: (realms_bytes.len() + 6) as u16,
             unknown: 0,
-            realms_count,
-            realms: realms_bytes,
+            realms,
             unknown2: 0x0010,
-        }.to_binary()?));
+        }.unpack()?));
 
         Ok(response)
     }
 }
 
 impl Handler {
-    fn generate_realm() -> Realm {
-        let mut realm = Realm::default();
-
-        let name: String = rand::thread_rng()
-            .sample_iter(rand::distributions::Alphanumeric)
-            .take(10)
-            .map(char::from)
-            .collect();
-
-        realm.icon = 1;
-        realm.lock = 0;
-        realm.flags = 1;
-        realm.name = name;
-        realm.address = format!("127.0.0.1:{}", WORLD_PORT);
-        realm.timezone = 1;
-        realm.server_id = rand::thread_rng().gen_range(0..=100);
-
-        realm
+    fn build_realms(options: &RunOptions, account: &str) -> Vec<Realm> {
+        let player_limit = options.config.world.player_limit;
+
+        options.config.realms.iter().map(|config| {
+            let characters = options.characters.count(account, config.id);
+            let online = options.realm_status.online(config.id).count();
+            let listening = options.realm_status.is_listening(config.id);
+
+            Realm {
+                icon: config.icon,
+                lock: config.locked as u8,
+                flags: Self::flags(config, online, player_limit, listening),
+                name: config.name.clone(),
+                address: options.config.realm_address(config),
+                population: config.population.unwrap_or_else(|| Self::population(online, player_limit)),
+                characters: characters.min(u8::MAX as usize) as u8,
+                timezone: config.timezone,
+                server_id: config.id,
+                build: config.build.clone(),
+            }
+        }).collect()
     }
 
-    fn generate_unique_realms() -> Vec<Realm> {
-        let mut rng = rand::thread_rng();
-        let random_count: usize = rng.gen_range(1..=10);
+    // full realm is never shown as recommended or for new players
+    fn flags(config: &RealmConfig, online: usize, player_limit: usize, listening: bool) -> u8 {
+        let mut flags = config.flags;
+        if config.build.is_some() {
+            flags |= RealmFlags::SPECIFY_BUILD;
+        }
+        if config.recommended {
+            flags |= RealmFlags::RECOMMENDED;
+        }
+        if config.new_players {
+            flags |= RealmFlags::NEW_PLAYERS;
+        }
+        if player_limit > 0 && online >= player_limit {
+            flags = flags & !(RealmFlags::RECOMMENDED | RealmFlags::NEW_PLAYERS) | RealmFlags::FULL;
+        }
+        if !listening {
+            flags |= RealmFlags::OFFLINE;
+        }
 
-        let mut realms = Vec::new();
-        let mut generated_names = std::collections::HashSet::new();
+        flags
+    }
 
-        while realms.len() < random_count {
-            let realm = Self::generate_realm();
+    // client shows low population below 1.0, medium below 2.0 and high otherwise
+    fn population(online: usize, player_limit: usize) -> f32 {
+        let capacity = match player_limit {
+            0 => DEFAULT_CAPACITY,
+            limit => limit,
+        };
 
-            if generated_names.insert(realm.name.clone()) {
-                realms.push(realm);
-            }
+        (2.0 * online as f32 / capacity as f32).min(2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use tentacli::traits::BinaryConverter;
+
+    use crate::primary::characters::InMemoryCharacterRepository;
+    use crate::primary::config::{Config, RealmConfig};
+    use crate::primary::server::auth::realmlist::Handler;
+    use crate::primary::server::auth::types::RealmFlags;
+    use crate::primary::server::Opcode;
+    use crate::primary::traits::packet_handler::PacketHandler;
+    use crate::primary::traits::server::{handle_single, test_input, RunOptions};
+    use crate::primary::types::{Character, Session};
+    use crate::primary::types::fields::realms::{RealmBuild, Realms};
+
+    #[test]
+    fn test_realms_are_built_from_config() {
+        let characters = InMemoryCharacterRepository::new();
+        for realm_id in [1, 1, 2] {
+            characters.insert(Character { account: "TEST".to_string(), realm_id, ..Character::default() });
         }
+        characters.insert(Character { account: "OTHER".to_string(), realm_id: 1, ..Character::default() });
+
+        let mut config = Config::default();
+        config.realms.push(RealmConfig {
+            id: 2,
+            name: "Second".to_string(),
+            address: Some("10.0.0.1:9000".to_string()),
+            locked: true,
+            ..RealmConfig::default()
+        });
+
+        let options = build_options(config, characters);
+        options.realm_status.set_listening(1, true);
+        options.realm_status.set_listening(2, true);
+
+        let realms = Handler::build_realms(&options, "test");
+        assert_eq!(realms.len(), 2);
+
+        assert_eq!(realms[0].name, "Tine");
+        assert_eq!(realms[0].address, "127.0.0.1:8999");
+        assert_eq!(realms[0].characters, 2);
+        assert_eq!(realms[0].lock, 0);
+
+        assert_eq!(realms[1].server_id, 2);
+        assert_eq!(realms[1].address, "10.0.0.1:9000");
+        assert_eq!(realms[1].characters, 1);
+        assert_eq!(realms[1].lock, 1);
+    }
+
+    #[test]
+    fn test_realm_status_flags() {
+        let mut config = Config::default();
+        config.world.player_limit = 2;
+        config.realms[0].recommended = true;
+        config.realms[0].new_players = true;
+        config.realms.push(RealmConfig { id: 2, ..RealmConfig::default() });
+
+        let options = build_options(config, InMemoryCharacterRepository::new());
+        options.realm_status.set_listening(1, true);
+
+        let online = options.realm_status.online(1);
+        let guard = online.enter("FIRST");
+
+        let realms = Handler::build_realms(&options, "test");
+        assert_eq!(realms[0].flags, RealmFlags::RECOMMENDED | RealmFlags::NEW_PLAYERS);
+        assert_eq!(realms[0].population, 1.0);
+        assert_eq!(realms[1].flags, RealmFlags::OFFLINE);
+        assert_eq!(realms[1].population, 0.0);
+
+        let _other_guard = online.enter("SECOND");
+        let realms = Handler::build_realms(&options, "test");
+        assert_eq!(realms[0].flags, RealmFlags::FULL);
+        assert_eq!(realms[0].population, 2.0);
+
+        drop(guard);
+        options.realm_status.set_listening(1, false);
+        let realms = Handler::build_realms(&options, "test");
+        assert_eq!(realms[0].flags, RealmFlags::RECOMMENDED | RealmFlags::NEW_PLAYERS | RealmFlags::OFFLINE);
+    }
+
+    #[tokio::test]
+    async fn test_realm_list_packet() {
+        let mut config = Config::default();
+        config.realms[0].build = Some(RealmBuild { major: 3, minor: 3, bugfix: 5, build: 12340 });
+
+        let options = Arc::new(build_options(config, InMemoryCharacterRepository::new()));
+        let session = Session { account: Some("TEST".to_string()), ..Session::new() };
+        let mut input = test_input(&options, session, Opcode::REALM_LIST as u32, vec![0; 4]);
+        let packet = handle_single(&mut Handler, &mut input).await;
+
+        assert_eq!(u16::from_le_bytes([packet[1], packet[2]]) as usize, packet.len() - 3);
+        assert_eq!(packet[packet.len() - 2..], [0x10, 0x00]);
 
-        realms
+        let Realms(realms) = Realms::read_from(&packet[7..]).unwrap();
+        assert_eq!(realms.len(), 1);
+        assert_eq!(realms[0].name, "Tine");
+        assert_eq!(realms[0].flags & RealmFlags::SPECIFY_BUILD, RealmFlags::SPECIFY_BUILD);
+        assert_eq!(realms[0].build.as_ref().map(|build| build.build), Some(12340));
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_unauthenticated_realm_list() {
+        let options = Arc::new(build_options(Config::default(), InMemoryCharacterRepository::new()));
+        let session = Session { challenged_account: Some("TEST".to_string()), ..Session::new() };
+        let mut input = test_input(&options, session, Opcode::REALM_LIST as u32, vec![0; 4]);
+
+        assert!(Handler.handle(&mut input).await.is_err());
+    }
+
+    fn build_options(config: Config, characters: InMemoryCharacterRepository) -> RunOptions {
+        RunOptions {
+            characters: Arc::new(characters),
+            ..RunOptions::for_tests(config)
+        }
+    }
+}
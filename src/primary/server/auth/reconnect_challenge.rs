@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tentacli::packet::idewave::LoginPacket;
+
+use crate::with_opcode;
+use crate::primary::server::auth::login_challenge;
+use crate::primary::server::auth::types::AuthResult;
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::types::{HandlerInput, HandlerOutput, HandlerResult};
+
+with_opcode! {
+    @login_opcode(Opcode::RECONNECT_CHALLENGE)
+    #[derive(LoginPacket, Serialize, Deserialize, Debug)]
+    struct Outcome {
+        code: u8,
+        reconnect_proof: [u8; 16],
+        version_challenge: [u8; 16],
+    }
+}
+
+with_opcode! {
+    @login_opcode(Opcode::RECONNECT_CHALLENGE)
+    #[derive(LoginPacket, Serialize, Deserialize, Debug)]
+    struct ErrorOutcome {
+        code: u8,
+    }
+}
+
+pub struct Handler;
+#[async_trait]
+impl PacketHandler for Handler {
+    async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
+        let mut response = Vec::new();
+
+        // reconnect challenge has the same layout as the login challenge
+        let income = match input.data.len() < login_challenge::MIN_INCOME_SIZE {
+            true => None,
+            false => login_challenge::Income::from_binary(&input.data).ok(),
+        };
+        let Some((login_challenge::Income { build, platform, account, .. }, json_details)) = income else {
+            return Self::reject(AuthResult::FAIL_VERSION_INVALID);
+        };
+        input.json_details = Some(json_details);
+
+        if !login_challenge::Handler::is_client_allowed(&input.options, build, &platform) {
+            return Self::reject(AuthResult::FAIL_VERSION_INVALID);
+        }
+
+        let account = account.to_uppercase();
+        if input.options.sessions.get(&account).is_none() {
+            return Self::reject(AuthResult::FAIL_UNKNOWN_ACCOUNT);
+        }
+
+        let reconnect_proof: [u8; 16] = rand::random();
+
+        response.push(HandlerOutput::Data(Outcome {
+            code: AuthResult::SUCCESS,
+            reconnect_proof,
+            version_challenge: login_challenge::VERSION_CHALLENGE,
+        }.unpack()?));
+
+        let mut session = input.session.lock().unwrap();
+        session.challenged_account = Some(account);
+        session.reconnect_proof = Some(reconnect_proof);
+
+        Ok(response)
+    }
+}
+
+impl Handler {
+    fn reject(code: u8) -> HandlerResult {
+        Ok(vec![HandlerOutput::Data(ErrorOutcome { code }.unpack()?)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::primary::config::Config;
+    use crate::primary::server::auth::login_challenge::VERSION_CHALLENGE;
+    use crate::primary::server::auth::reconnect_challenge::Handler;
+    use crate::primary::server::auth::types::AuthResult;
+    use crate::primary::server::{Opcode, Sessions};
+    use crate::primary::traits::server::{handle_single, test_input, RunOptions};
+    use crate::primary::types::Session;
+
+    fn build_income(account: &str) -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend(b"WoW\0");
+        body.extend([3, 3, 5]);
+        body.extend(12340u16.to_le_bytes());
+        body.extend(b"68x\0");
+        body.extend(b"niW\0");
+        body.extend(b"SUne");
+        body.extend(0u32.to_le_bytes());
+        body.extend([127, 0, 0, 1]);
+        body.push(account.len() as u8);
+        body.extend(account.as_bytes());
+
+        [vec![2], (body.len() as u16).to_le_bytes().to_vec(), body].concat()
+    }
+
+    // returns the packet and the reconnect proof kept in the session
+    async fn handle(sessions: Sessions) -> (Vec<u8>, Option<[u8; 16]>) {
+        let options = Arc::new(RunOptions {
+            sessions: Arc::new(sessions),
+            ..RunOptions::for_tests(Config::default())
+        });
+        let mut input = test_input(&options, Session::new(), Opcode::RECONNECT_CHALLENGE as u32, build_income("test"));
+
+        let packet = handle_single(&mut Handler, &mut input).await;
+        let reconnect_proof = input.session.lock().unwrap().reconnect_proof;
+
+        (packet, reconnect_proof)
+    }
+
+    #[tokio::test]
+    async fn test_success_layout() {
+        let sessions = Sessions::new(Duration::from_secs(60));
+        sessions.insert("TEST", vec![1; 40]);
+
+        let (packet, reconnect_proof) = handle(sessions).await;
+
+        // opcode, result, reconnect proof and version challenge
+        assert_eq!(packet.len(), 34);
+        assert_eq!(packet[..2], [Opcode::RECONNECT_CHALLENGE, AuthResult::SUCCESS]);
+        assert_eq!(packet[2..18], reconnect_proof.unwrap());
+        assert_eq!(packet[18..], VERSION_CHALLENGE);
+    }
+
+    #[tokio::test]
+    async fn test_error_layout() {
+        let (packet, _) = handle(Sessions::new(Duration::from_secs(60))).await;
+
+        assert_eq!(packet, vec![Opcode::RECONNECT_CHALLENGE, AuthResult::FAIL_UNKNOWN_ACCOUNT]);
+    }
+}
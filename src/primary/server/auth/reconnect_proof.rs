@@ -0,0 +1,137 @@
+use anyhow::anyhow;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use tentacli::packet::idewave::LoginPacket;
+
+use crate::with_opcode;
+use crate::primary::server::auth::types::AuthResult;
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::types::{HandlerInput, HandlerOutput, HandlerResult};
+
+with_opcode! {
+    @login_opcode(Opcode::RECONNECT_PROOF)
+    #[derive(LoginPacket, Serialize, Deserialize, Debug)]
+    struct Income {
+        proof_data: [u8; 16],
+        client_proof: [u8; 20],
+        client_checksum: [u8; 20],
+        keys_count: u8,
+    }
+}
+
+with_opcode! {
+    @login_opcode(Opcode::RECONNECT_PROOF)
+    #[derive(LoginPacket, Serialize, Deserialize, Debug)]
+    struct Outcome {
+        error: u8,
+        login_flags: u16,
+    }
+}
+
+pub struct Handler;
+#[async_trait]
+impl PacketHandler for Handler {
+    async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
+        let mut response = Vec::new();
+        let (Income { proof_data, client_proof, .. }, json_details) = Income::from_binary(&input.data)?;
+        input.json_details = Some(json_details);
+
+        let (account, reconnect_proof) = {
+            let mut session = input.session.lock().unwrap();
+            let reconnect_proof = session.reconnect_proof.take()
+                .ok_or_else(|| anyhow!("RECONNECT_PROOF received before RECONNECT_CHALLENGE"))?;
+
+            (session.challenged_account.take().unwrap_or_default(), reconnect_proof)
+        };
+
+        let session_key = input.options.sessions.get(&account);
+
+        let error = match session_key {
+            Some(session_key) => {
+                let server_proof = Sha1::new()
+                    .chain(account.as_bytes())
+                    .chain(proof_data)
+                    .chain(reconnect_proof)
+                    .chain(session_key)
+                    .finalize();
+
+                if server_proof.as_slice() == client_proof {
+                    AuthResult::SUCCESS
+                } else {
+                    AuthResult::FAIL_INCORRECT_PASSWORD
+                }
+            },
+            None => AuthResult::FAIL_UNKNOWN_ACCOUNT,
+        };
+
+        if error == AuthResult::SUCCESS {
+            input.options.sessions.touch(&account);
+            input.session.lock().unwrap().account = Some(account);
+        }
+
+        response.push(HandlerOutput::Data(Outcome {
+            error,
+            login_flags: 0,
+        }.unpack()?));
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use sha1::{Digest, Sha1};
+
+    use crate::primary::config::Config;
+    use crate::primary::server::auth::reconnect_proof::Handler;
+    use crate::primary::server::auth::types::AuthResult;
+    use crate::primary::server::{Opcode, Sessions};
+    use crate::primary::traits::server::{handle_single, test_input, RunOptions};
+    use crate::primary::types::Session;
+
+    async fn handle(session_key: &[u8], client_key: &[u8]) -> Vec<u8> {
+        let reconnect_proof: [u8; 16] = rand::random();
+        let proof_data: [u8; 16] = rand::random();
+
+        let sessions = Sessions::new(Duration::from_secs(60));
+        sessions.insert("TEST", session_key.to_vec());
+
+        let session = Session {
+            challenged_account: Some("TEST".to_string()),
+            reconnect_proof: Some(reconnect_proof),
+            ..Session::new()
+        };
+
+        let client_proof = Sha1::new()
+            .chain("TEST")
+            .chain(proof_data)
+            .chain(reconnect_proof)
+            .chain(client_key)
+            .finalize();
+
+        let options = Arc::new(RunOptions {
+            sessions: Arc::new(sessions),
+            ..RunOptions::for_tests(Config::default())
+        });
+        let data = [&proof_data[..], &client_proof[..], &[0; 20], &[0]].concat();
+
+        handle_single(&mut Handler, &mut test_input(&options, session, Opcode::RECONNECT_PROOF as u32, data)).await
+    }
+
+    #[tokio::test]
+    async fn test_valid_reconnect_proof() {
+        let session_key = rand::random::<[u8; 32]>();
+        assert_eq!(handle(&session_key, &session_key).await, vec![3, AuthResult::SUCCESS, 0, 0]);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_reconnect_proof() {
+        let session_key = rand::random::<[u8; 32]>();
+        let packet = handle(&session_key, &[0; 40]).await;
+        assert_eq!(packet, vec![3, AuthResult::FAIL_INCORRECT_PASSWORD, 0, 0]);
+    }
+}
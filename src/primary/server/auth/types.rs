@@ -6,4 +6,64 @@ impl AccountFlags {
     pub const ACCOUNT_FLAG_GM: u32      = 0x00000001;
     pub const ACCOUNT_FLAG_TRIAL: u32   = 0x00000008;
     pub const ACCOUNT_FLAG_PROPASS: u32 = 0x00800000;
-}
\ No newline at end of file
+}
+
+#[non_exhaustive]
+pub struct RealmType;
+
+#[allow(dead_code)]
+impl RealmType {
+    pub const NORMAL: u8 = 0;
+    pub const PVP: u8    = 1;
+    pub const RP: u8     = 6;
+    pub const RP_PVP: u8 = 8;
+}
+
+#[non_exhaustive]
+pub struct RealmFlags;
+
+#[allow(dead_code)]
+impl RealmFlags {
+    pub const NONE: u8             = 0x00;
+    pub const VERSION_MISMATCH: u8 = 0x01;
+    pub const OFFLINE: u8          = 0x02;
+    pub const SPECIFY_BUILD: u8    = 0x04;
+    pub const RECOMMENDED: u8      = 0x20;
+    pub const NEW_PLAYERS: u8      = 0x40;
+    pub const FULL: u8             = 0x80;
+}
+
+#[non_exhaustive]
+pub struct AuthResult;
+
+#[allow(dead_code)]
+impl AuthResult {
+    pub const SUCCESS: u8                     = 0x00;
+    pub const FAIL_UNKNOWN0: u8               = 0x01;
+    pub const FAIL_UNKNOWN1: u8               = 0x02;
+    pub const FAIL_BANNED: u8                 = 0x03;
+    pub const FAIL_UNKNOWN_ACCOUNT: u8        = 0x04;
+    pub const FAIL_INCORRECT_PASSWORD: u8     = 0x05;
+    pub const FAIL_ALREADY_ONLINE: u8         = 0x06;
+    pub const FAIL_NO_TIME: u8                = 0x07;
+    pub const FAIL_DB_BUSY: u8                = 0x08;
+    pub const FAIL_VERSION_INVALID: u8        = 0x09;
+    pub const FAIL_VERSION_UPDATE: u8         = 0x0A;
+    pub const FAIL_INVALID_SERVER: u8         = 0x0B;
+    pub const FAIL_SUSPENDED: u8              = 0x0C;
+    pub const FAIL_NO_ACCESS: u8              = 0x0D;
+    pub const SUCCESS_SURVEY: u8              = 0x0E;
+    pub const FAIL_PARENT_CONTROL: u8         = 0x0F;
+    pub const FAIL_LOCKED_ENFORCED: u8        = 0x10;
+    pub const FAIL_TRIAL_ENDED: u8            = 0x11;
+    pub const FAIL_USE_BATTLENET: u8          = 0x12;
+    pub const FAIL_ANTI_INDULGENCE: u8        = 0x13;
+    pub const FAIL_EXPIRED: u8                = 0x14;
+    pub const FAIL_NO_GAME_ACCOUNT: u8        = 0x15;
+    pub const FAIL_CHARGEBACK: u8             = 0x16;
+    pub const FAIL_GAME_ROOM_WITHOUT_BNET: u8 = 0x17;
+    pub const FAIL_GAME_ACCOUNT_LOCKED: u8    = 0x18;
+    pub const FAIL_UNLOCKABLE_LOCK: u8        = 0x19;
+    pub const FAIL_CONVERSION_REQUIRED: u8    = 0x20;
+    pub const FAIL_DISCONNECTED: u8           = 0xFF;
+}
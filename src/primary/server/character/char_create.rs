@@ -0,0 +1,113 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tentacli::packet::custom_fields::TerminatedString;
+use tentacli::packet::idewave::WorldPacket;
+
+use crate::with_opcode;
+use crate::primary::server::character::{authorized, pvp_teams_violation, response_code, start_position};
+use crate::primary::server::character::name::normalize_name;
+use crate::primary::server::character::types::{AtLoginFlags, Class, Gender, Race};
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::server::realm::types::ResponseCode;
+use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::types::{Character, HandlerInput, HandlerOutput, HandlerResult};
+
+// characters of one account on one realm
+const MAX_CHARACTERS: usize = 10;
+const DEATH_KNIGHT_LEVEL: u8 = 55;
+
+with_opcode! {
+    @world_opcode(Opcode::CMSG_CHAR_CREATE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Income {
+        name: TerminatedString,
+        race: u8,
+        class: u8,
+        gender: u8,
+        skin: u8,
+        face: u8,
+        hair_style: u8,
+        hair_color: u8,
+        facial_hair: u8,
+        outfit_id: u8,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_CHAR_CREATE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Outcome {
+        result: u8,
+    }
+}
+
+pub struct Handler;
+#[async_trait]
+impl PacketHandler for Handler {
+    async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
+        let (income, json_details) = Income::from_binary(&input.data)?;
+        input.json_details = Some(json_details);
+
+        let (account, realm_id) = authorized(input)?;
+        let options = &input.options;
+
+        if !Race::classes(income.race).contains(&income.class) || income.gender > Gender::FEMALE {
+            return Self::reply(ResponseCode::CHAR_CREATE_FAILED);
+        }
+
+        let name = match normalize_name(&income.name.0) {
+            Ok(name) => name,
+            Err(code) => return Self::reply(code),
+        };
+
+        let characters = options.characters.list(&account, realm_id);
+        if characters.len() >= MAX_CHARACTERS {
+            return Self::reply(ResponseCode::CHAR_CREATE_SERVER_LIMIT);
+        }
+
+        if income.class == Class::DEATH_KNIGHT && characters.iter().any(|character| character.class == Class::DEATH_KNIGHT) {
+            return Self::reply(ResponseCode::CHAR_CREATE_UNIQUE_CLASS_LIMIT);
+        }
+
+        if pvp_teams_violation(input, &account, realm_id, income.race, None) {
+            return Self::reply(ResponseCode::CHAR_CREATE_PVP_TEAMS_VIOLATION);
+        }
+
+        let (map, zone, x, y, z, orientation) = start_position(income.race, income.class);
+        if let Err(err) = options.characters.create(Character {
+            account,
+            realm_id,
+            name,
+            race: income.race,
+            class: income.class,
+            gender: income.gender,
+            skin: income.skin,
+            face: income.face,
+            hair_style: income.hair_style,
+            hair_color: income.hair_color,
+            facial_hair: income.facial_hair,
+            level: match income.class {
+                Class::DEATH_KNIGHT => DEATH_KNIGHT_LEVEL,
+                _ => 1,
+            },
+            zone,
+            map,
+            x,
+            y,
+            z,
+            orientation,
+            at_login: AtLoginFlags::FIRST | options.config.world.character_at_login,
+            ..Character::default()
+        }) {
+            return Self::reply(response_code(err));
+        }
+
+        Self::reply(ResponseCode::CHAR_CREATE_SUCCESS)
+    }
+}
+
+impl Handler {
+    fn reply(result: u8) -> HandlerResult {
+        Ok(vec![HandlerOutput::Data(Outcome { result }.unpack()?)])
+    }
+}
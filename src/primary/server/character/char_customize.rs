@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tentacli::packet::custom_fields::TerminatedString;
+use tentacli::packet::idewave::WorldPacket;
+
+use crate::with_opcode;
+use crate::primary::server::character::{authorized, own_character, response_code};
+use crate::primary::server::character::name::normalize_name;
+use crate::primary::server::character::types::{AtLoginFlags, Gender};
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::server::realm::types::ResponseCode;
+use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::types::{Character, HandlerInput, HandlerOutput, HandlerResult};
+
+with_opcode! {
+    @world_opcode(Opcode::CMSG_CHAR_CUSTOMIZE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Income {
+        guid: u64,
+        name: TerminatedString,
+        gender: u8,
+        skin: u8,
+        hair_color: u8,
+        hair_style: u8,
+        facial_hair: u8,
+        face: u8,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_CHAR_CUSTOMIZE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Outcome {
+        result: u8,
+        guid: u64,
+        name: TerminatedString,
+        gender: u8,
+        skin: u8,
+        face: u8,
+        hair_style: u8,
+        hair_color: u8,
+        facial_hair: u8,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_CHAR_CUSTOMIZE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct ErrorOutcome {
+        result: u8,
+    }
+}
+
+pub struct Handler;
+#[async_trait]
+impl PacketHandler for Handler {
+    async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
+        let (income, json_details) = Income::from_binary(&input.data)?;
+        input.json_details = Some(json_details);
+
+        let (account, realm_id) = authorized(input)?;
+
+        let Some(character) = own_character(input, income.guid, &account, realm_id)
+            .filter(|character| character.at_login & AtLoginFlags::CUSTOMIZE != 0) else {
+            return Self::reject(ResponseCode::CHAR_CREATE_ERROR);
+        };
+
+        if income.gender > Gender::FEMALE {
+            return Self::reject(ResponseCode::CHAR_CREATE_ERROR);
+        }
+
+        // the name can be changed on the same screen
+        let name = match normalize_name(&income.name.0) {
+            Ok(name) => name,
+            Err(code) => return Self::reject(code),
+        };
+
+        if let Err(err) = input.options.characters.update(Character {
+            name: name.clone(),
+            gender: income.gender,
+            skin: income.skin,
+            face: income.face,
+            hair_style: income.hair_style,
+            hair_color: income.hair_color,
+            facial_hair: income.facial_hair,
+            at_login: character.at_login & !AtLoginFlags::CUSTOMIZE,
+            ..character
+        }) {
+            return Self::reject(response_code(err));
+        }
+
+        Ok(vec![HandlerOutput::Data(Outcome {
+            result: ResponseCode::RESPONSE_SUCCESS,
+            guid: income.guid,
+            name: TerminatedString(name),
+            gender: income.gender,
+            skin: income.skin,
+            face: income.face,
+            hair_style: income.hair_style,
+            hair_color: income.hair_color,
+            facial_hair: income.facial_hair,
+        }.unpack()?)])
+    }
+}
+
+impl Handler {
+    fn reject(result: u8) -> HandlerResult {
+        Ok(vec![HandlerOutput::Data(ErrorOutcome { result }.unpack()?)])
+    }
+}
@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tentacli::packet::idewave::WorldPacket;
+
+use crate::with_opcode;
+use crate::primary::server::character::{authorized, own_character};
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::server::realm::types::ResponseCode;
+use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::types::{HandlerInput, HandlerOutput, HandlerResult};
+
+with_opcode! {
+    @world_opcode(Opcode::CMSG_CHAR_DELETE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Income {
+        guid: u64,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_CHAR_DELETE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Outcome {
+        result: u8,
+    }
+}
+
+pub struct Handler;
+#[async_trait]
+impl PacketHandler for Handler {
+    async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
+        let (Income { guid }, json_details) = Income::from_binary(&input.data)?;
+        input.json_details = Some(json_details);
+
+        let (account, realm_id) = authorized(input)?;
+        let result = match own_character(input, guid, &account, realm_id) {
+            Some(_) => {
+                input.options.characters.delete(guid);
+                ResponseCode::CHAR_DELETE_SUCCESS
+            },
+            None => ResponseCode::CHAR_DELETE_FAILED,
+        };
+
+        Ok(vec![HandlerOutput::Data(Outcome { result }.unpack()?)])
+    }
+}
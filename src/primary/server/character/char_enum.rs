@@ -0,0 +1,89 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tentacli::packet::idewave::WorldPacket;
+
+use crate::with_opcode;
+use crate::primary::server::character::authorized;
+use crate::primary::server::character::types::{AtLoginFlags, CharacterFlags, CustomizeFlags};
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::types::{Character, HandlerInput, HandlerOutput, HandlerResult};
+use crate::primary::types::fields::characters::{CharacterEntry, Characters};
+
+with_opcode! {
+    @world_opcode(Opcode::CMSG_CHAR_ENUM)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Income {}
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_CHAR_ENUM)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Outcome {
+        characters: Characters,
+    }
+}
+
+pub struct Handler;
+#[async_trait]
+impl PacketHandler for Handler {
+    async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
+        let (_, json_details) = Income::from_binary(&input.data)?;
+        input.json_details = Some(json_details);
+
+        let (account, realm_id) = authorized(input)?;
+        let characters = input.options.characters.list(&account, realm_id)
+            .iter()
+            .map(Self::entry)
+            .collect();
+
+        Ok(vec![HandlerOutput::Data(Outcome {
+            characters: Characters(characters),
+        }.unpack()?)])
+    }
+}
+
+impl Handler {
+    fn entry(character: &Character) -> CharacterEntry {
+        let at_login = |flag: u16| character.at_login & flag != 0;
+
+        let mut flags = CharacterFlags::NONE;
+        if at_login(AtLoginFlags::RENAME) {
+            flags |= CharacterFlags::RENAME;
+        }
+
+        let mut customize_flags = CustomizeFlags::NONE;
+        if at_login(AtLoginFlags::CUSTOMIZE) {
+            customize_flags |= CustomizeFlags::CUSTOMIZE;
+        }
+        if at_login(AtLoginFlags::CHANGE_FACTION) {
+            customize_flags |= CustomizeFlags::FACTION;
+        }
+        if at_login(AtLoginFlags::CHANGE_RACE) {
+            customize_flags |= CustomizeFlags::RACE;
+        }
+
+        CharacterEntry {
+            guid: character.guid,
+            name: character.name.clone(),
+            race: character.race,
+            class: character.class,
+            gender: character.gender,
+            skin: character.skin,
+            face: character.face,
+            hair_style: character.hair_style,
+            hair_color: character.hair_color,
+            facial_hair: character.facial_hair,
+            level: character.level,
+            zone: character.zone,
+            map: character.map,
+            x: character.x,
+            y: character.y,
+            z: character.z,
+            flags,
+            customize_flags,
+            first_login: at_login(AtLoginFlags::FIRST) as u8,
+            ..CharacterEntry::default()
+        }
+    }
+}
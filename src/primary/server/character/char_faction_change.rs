@@ -0,0 +1,140 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tentacli::packet::custom_fields::TerminatedString;
+use tentacli::packet::idewave::WorldPacket;
+
+use crate::with_opcode;
+use crate::primary::server::character::{authorized, own_character, pvp_teams_violation, response_code, start_position};
+use crate::primary::server::character::name::normalize_name;
+use crate::primary::server::character::types::{AtLoginFlags, Gender, Race};
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::server::realm::types::ResponseCode;
+use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::types::{Character, HandlerInput, HandlerOutput, HandlerResult};
+
+// CMSG_CHAR_RACE_CHANGE has the same layout
+with_opcode! {
+    @world_opcode(Opcode::CMSG_CHAR_FACTION_CHANGE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Income {
+        guid: u64,
+        name: TerminatedString,
+        gender: u8,
+        skin: u8,
+        hair_color: u8,
+        hair_style: u8,
+        facial_hair: u8,
+        face: u8,
+        race: u8,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_CHAR_FACTION_CHANGE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Outcome {
+        result: u8,
+        guid: u64,
+        name: TerminatedString,
+        gender: u8,
+        skin: u8,
+        face: u8,
+        hair_style: u8,
+        hair_color: u8,
+        facial_hair: u8,
+        race: u8,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_CHAR_FACTION_CHANGE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct ErrorOutcome {
+        result: u8,
+    }
+}
+
+pub struct Handler;
+#[async_trait]
+impl PacketHandler for Handler {
+    async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
+        let (income, json_details) = Income::from_binary(&input.data)?;
+        input.json_details = Some(json_details);
+
+        let (account, realm_id) = authorized(input)?;
+
+        // race change keeps the faction, faction change switches it
+        let faction_change = input.opcode as u32 == Opcode::CMSG_CHAR_FACTION_CHANGE;
+        let flag = match faction_change {
+            true => AtLoginFlags::CHANGE_FACTION,
+            false => AtLoginFlags::CHANGE_RACE,
+        };
+
+        let Some(character) = own_character(input, income.guid, &account, realm_id)
+            .filter(|character| character.at_login & flag != 0) else {
+            return Self::reject(ResponseCode::CHAR_CREATE_ERROR);
+        };
+
+        let switches_faction = Race::is_alliance(character.race) != Race::is_alliance(income.race);
+        if switches_faction != faction_change
+            || !Race::classes(income.race).contains(&character.class)
+            || income.gender > Gender::FEMALE {
+            return Self::reject(ResponseCode::CHAR_CREATE_ERROR);
+        }
+
+        if switches_faction && pvp_teams_violation(input, &account, realm_id, income.race, Some(income.guid)) {
+            return Self::reject(ResponseCode::CHAR_CREATE_PVP_TEAMS_VIOLATION);
+        }
+
+        let name = match normalize_name(&income.name.0) {
+            Ok(name) => name,
+            Err(code) => return Self::reject(code),
+        };
+
+        // the character is moved to the start location of the new faction
+        let (map, zone, x, y, z, orientation) = match faction_change {
+            true => start_position(income.race, character.class),
+            false => (character.map, character.zone, character.x, character.y, character.z, character.orientation),
+        };
+
+        if let Err(err) = input.options.characters.update(Character {
+            name: name.clone(),
+            race: income.race,
+            gender: income.gender,
+            skin: income.skin,
+            face: income.face,
+            hair_style: income.hair_style,
+            hair_color: income.hair_color,
+            facial_hair: income.facial_hair,
+            map,
+            zone,
+            x,
+            y,
+            z,
+            orientation,
+            at_login: character.at_login & !flag,
+            ..character
+        }) {
+            return Self::reject(response_code(err));
+        }
+
+        Ok(vec![HandlerOutput::Data(Outcome {
+            result: ResponseCode::RESPONSE_SUCCESS,
+            guid: income.guid,
+            name: TerminatedString(name),
+            gender: income.gender,
+            skin: income.skin,
+            face: income.face,
+            hair_style: income.hair_style,
+            hair_color: income.hair_color,
+            facial_hair: income.facial_hair,
+            race: income.race,
+        }.unpack()?)])
+    }
+}
+
+impl Handler {
+    fn reject(result: u8) -> HandlerResult {
+        Ok(vec![HandlerOutput::Data(ErrorOutcome { result }.unpack()?)])
+    }
+}
@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tentacli::packet::custom_fields::TerminatedString;
+use tentacli::packet::idewave::WorldPacket;
+
+use crate::with_opcode;
+use crate::primary::server::character::{authorized, own_character, response_code};
+use crate::primary::server::character::name::normalize_name;
+use crate::primary::server::character::types::AtLoginFlags;
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::server::realm::types::ResponseCode;
+use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::types::{Character, HandlerInput, HandlerOutput, HandlerResult};
+
+with_opcode! {
+    @world_opcode(Opcode::CMSG_CHAR_RENAME)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Income {
+        guid: u64,
+        name: TerminatedString,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_CHAR_RENAME)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Outcome {
+        result: u8,
+        guid: u64,
+        name: TerminatedString,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_CHAR_RENAME)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct ErrorOutcome {
+        result: u8,
+    }
+}
+
+pub struct Handler;
+#[async_trait]
+impl PacketHandler for Handler {
+    async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
+        let (Income { guid, name }, json_details) = Income::from_binary(&input.data)?;
+        input.json_details = Some(json_details);
+
+        let (account, realm_id) = authorized(input)?;
+
+        // the client can rename only characters marked for it
+        let Some(character) = own_character(input, guid, &account, realm_id)
+            .filter(|character| character.at_login & AtLoginFlags::RENAME != 0) else {
+            return Self::reject(ResponseCode::CHAR_CREATE_ERROR);
+        };
+
+        let name = match normalize_name(&name.0) {
+            Ok(name) => name,
+            Err(code) => return Self::reject(code),
+        };
+
+        if let Err(err) = input.options.characters.update(Character {
+            name: name.clone(),
+            at_login: character.at_login & !AtLoginFlags::RENAME,
+            ..character
+        }) {
+            return Self::reject(response_code(err));
+        }
+
+        Ok(vec![HandlerOutput::Data(Outcome {
+            result: ResponseCode::RESPONSE_SUCCESS,
+            guid,
+            name: TerminatedString(name),
+        }.unpack()?)])
+    }
+}
+
+impl Handler {
+    fn reject(result: u8) -> HandlerResult {
+        Ok(vec![HandlerOutput::Data(ErrorOutcome { result }.unpack()?)])
+    }
+}
@@ -0,0 +1,309 @@
+mod char_create;
+mod char_customize;
+mod char_delete;
+mod char_enum;
+mod char_faction_change;
+mod char_rename;
+mod name;
+mod player_login;
+pub(super) mod types;
+
+use anyhow::{anyhow, Result as AnyResult};
+
+use crate::primary::server::auth::types::RealmType;
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::server::realm::types::ResponseCode;
+use crate::primary::traits::character_repository::CharacterError;
+use crate::primary::traits::processor::Processor;
+use crate::primary::server::character::types::{Class, Race};
+use crate::primary::types::{Character, HandlerInput, ProcessorResult};
+
+pub struct CharacterProcessor;
+
+impl Processor for CharacterProcessor {
+    fn get_handlers(input: &mut HandlerInput) -> ProcessorResult {
+        let opcode = input.opcode as u32;
+
+        let handlers: ProcessorResult = match opcode {
+            Opcode::CMSG_CHAR_ENUM => {
+                vec![Box::new(char_enum::Handler)]
+            },
+            Opcode::CMSG_CHAR_CREATE => {
+                vec![Box::new(char_create::Handler)]
+            },
+            Opcode::CMSG_CHAR_DELETE => {
+                vec![Box::new(char_delete::Handler)]
+            },
+            Opcode::CMSG_CHAR_RENAME => {
+                vec![Box::new(char_rename::Handler)]
+            },
+            Opcode::CMSG_CHAR_CUSTOMIZE => {
+                vec![Box::new(char_customize::Handler)]
+            },
+            Opcode::CMSG_CHAR_FACTION_CHANGE | Opcode::CMSG_CHAR_RACE_CHANGE => {
+                vec![Box::new(char_faction_change::Handler)]
+            },
+            Opcode::CMSG_PLAYER_LOGIN => {
+                vec![Box::new(player_login::Handler)]
+            },
+            _ => vec![],
+        };
+
+        handlers
+    }
+}
+
+// returns account and realm of the session, characters are available only after CMSG_AUTH_SESSION
+fn authorized(input: &HandlerInput) -> AnyResult<(String, u8)> {
+    let session = input.session.lock().unwrap();
+    let account = session.account.clone().ok_or_else(|| anyhow!("Session is not authorized"))?;
+
+    Ok((account, session.realm_id))
+}
+
+// characters of other accounts and realms are not visible to the client
+fn own_character(input: &HandlerInput, guid: u64, account: &str, realm_id: u8) -> Option<Character> {
+    input.options.characters.get(guid)
+        .filter(|character| character.account == account && character.realm_id == realm_id)
+}
+
+// characters of one account cannot fight each other on pvp realms, except_guid is the character being changed
+fn pvp_teams_violation(input: &HandlerInput, account: &str, realm_id: u8, race: u8, except_guid: Option<u64>) -> bool {
+    let pvp = input.options.config.realms.iter()
+        .find(|realm| realm.id == realm_id)
+        .is_some_and(|realm| realm.icon == RealmType::PVP || realm.icon == RealmType::RP_PVP);
+    let alliance = Race::is_alliance(race);
+
+    pvp && input.options.characters.list(account, realm_id).iter()
+        .filter(|character| Some(character.guid) != except_guid)
+        .any(|character| Race::is_alliance(character.race) != alliance)
+}
+
+// the name can be taken by another character between the packet checks and the repository call
+fn response_code(error: CharacterError) -> u8 {
+    match error {
+        CharacterError::NameInUse => ResponseCode::CHAR_CREATE_NAME_IN_USE,
+        CharacterError::NotFound => ResponseCode::CHAR_CREATE_ERROR,
+    }
+}
+
+// map, zone, x, y, z and orientation from playercreateinfo
+fn start_position(race: u8, class: u8) -> (u32, u32, f32, f32, f32, f32) {
+    if class == Class::DEATH_KNIGHT {
+        return (609, 4298, 2355.84, -5664.77, 426.028, 3.65997);
+    }
+
+    match race {
+        Race::ORC | Race::TROLL => (1, 14, -618.518, -4251.67, 38.718, 0.0),
+        Race::DWARF | Race::GNOME => (0, 1, -6240.32, 331.033, 382.758, 6.17716),
+        Race::NIGHT_ELF => (1, 141, 10311.3, 832.463, 1326.41, 5.69632),
+        Race::UNDEAD => (0, 85, 1676.71, 1678.31, 121.67, 2.70526),
+        Race::TAUREN => (1, 215, -2917.58, -257.98, 52.9968, 0.0),
+        Race::BLOOD_ELF => (530, 3431, 10349.6, -6357.29, 33.4026, 5.31605),
+        Race::DRAENEI => (530, 3526, -3961.64, -13931.2, 100.615, 2.08364),
+        _ => (0, 12, -8949.95, -132.493, 83.5312, 0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use tentacli::traits::BinaryConverter;
+
+    use crate::primary::config::Config;
+    use crate::primary::server::auth::types::RealmType;
+    use crate::primary::server::character::CharacterProcessor;
+    use crate::primary::server::character::types::{AtLoginFlags, CharacterFlags, Class, CustomizeFlags, Race};
+    use crate::primary::server::realm::types::ResponseCode;
+    use crate::primary::server::Opcode;
+    use crate::primary::traits::processor::Processor;
+    use crate::primary::traits::server::{handle_single, test_input, RunOptions};
+    use crate::primary::types::{Character, Session};
+    use crate::primary::types::fields::characters::Characters;
+
+    fn build_options(config: Config) -> Arc<RunOptions> {
+        Arc::new(RunOptions::for_tests(config))
+    }
+
+    // returns packet body, the header is skipped
+    async fn handle(options: &Arc<RunOptions>, account: &str, opcode: u32, data: Vec<u8>) -> Vec<u8> {
+        let session = Session { account: Some(account.to_string()), realm_id: 1, ..Session::new() };
+        let mut input = test_input(options, session, opcode, data);
+
+        let mut handler = CharacterProcessor::get_handlers(&mut input).remove(0);
+        handle_single(handler.as_mut(), &mut input).await[6..].to_vec()
+    }
+
+    async fn create(options: &Arc<RunOptions>, account: &str, name: &str, race: u8, class: u8) -> u8 {
+        let mut data = name.as_bytes().to_vec();
+        data.extend([0, race, class, 0, 1, 2, 3, 4, 5, 0]);
+
+        handle(options, account, Opcode::CMSG_CHAR_CREATE, data).await[0]
+    }
+
+    async fn list(options: &Arc<RunOptions>, account: &str) -> Characters {
+        let body = handle(options, account, Opcode::CMSG_CHAR_ENUM, vec![]).await;
+
+        Characters::read_from(body.as_slice()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_enum_and_delete() {
+        let options = build_options(Config::default());
+
+        assert_eq!(create(&options, "TEST", "tHRALL", Race::ORC, Class::SHAMAN).await, ResponseCode::CHAR_CREATE_SUCCESS);
+        assert_eq!(create(&options, "TEST", "Arthas", Race::HUMAN, Class::DEATH_KNIGHT).await, ResponseCode::CHAR_CREATE_SUCCESS);
+        assert_eq!(create(&options, "OTHER", "Jaina", Race::HUMAN, Class::MAGE).await, ResponseCode::CHAR_CREATE_SUCCESS);
+
+        let Characters(characters) = list(&options, "TEST").await;
+        assert_eq!(characters.len(), 2);
+        assert_eq!(characters[0].name, "Thrall");
+        assert_eq!((characters[0].level, characters[0].map, characters[0].zone), (1, 1, 14));
+        assert_eq!((characters[0].skin, characters[0].facial_hair), (1, 5));
+        assert_eq!((characters[1].level, characters[1].map), (55, 609));
+
+        let jaina = options.characters.find_by_name("jaina", 1).unwrap();
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_DELETE, jaina.guid.to_le_bytes().to_vec()).await;
+        assert_eq!(body, vec![ResponseCode::CHAR_DELETE_FAILED]);
+
+        let guid = characters[0].guid;
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_DELETE, guid.to_le_bytes().to_vec()).await;
+        assert_eq!(body, vec![ResponseCode::CHAR_DELETE_SUCCESS]);
+        assert_eq!(list(&options, "TEST").await.0.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_errors() {
+        let options = build_options(Config::default());
+        assert_eq!(create(&options, "TEST", "Thrall", Race::ORC, Class::WARRIOR).await, ResponseCode::CHAR_CREATE_SUCCESS);
+
+        assert_eq!(create(&options, "TEST", "Rexxar", Race::ORC, Class::DRUID).await, ResponseCode::CHAR_CREATE_FAILED);
+        assert_eq!(create(&options, "TEST", "Rexxar", 9, Class::WARRIOR).await, ResponseCode::CHAR_CREATE_FAILED);
+        assert_eq!(create(&options, "TEST", "Rexxar1", Race::ORC, Class::HUNTER).await, ResponseCode::CHAR_NAME_INVALID_CHARACTER);
+        assert_eq!(create(&options, "OTHER", "THRALL", Race::ORC, Class::HUNTER).await, ResponseCode::CHAR_CREATE_NAME_IN_USE);
+
+        assert_eq!(create(&options, "TEST", "Arthas", Race::HUMAN, Class::DEATH_KNIGHT).await, ResponseCode::CHAR_CREATE_SUCCESS);
+        assert_eq!(create(&options, "TEST", "Koltira", Race::BLOOD_ELF, Class::DEATH_KNIGHT).await, ResponseCode::CHAR_CREATE_UNIQUE_CLASS_LIMIT);
+
+        for index in 0..8 {
+            let name = format!("Filler{}", "abcdefgh".chars().nth(index).unwrap());
+            options.characters.create(Character { account: "TEST".to_string(), realm_id: 1, name, ..Character::default() }).unwrap();
+        }
+        assert_eq!(create(&options, "TEST", "Rexxar", Race::ORC, Class::HUNTER).await, ResponseCode::CHAR_CREATE_SERVER_LIMIT);
+    }
+
+    #[tokio::test]
+    async fn test_pvp_teams_violation() {
+        let mut config = Config::default();
+        config.realms[0].icon = RealmType::PVP;
+        let options = build_options(config);
+
+        assert_eq!(create(&options, "TEST", "Thrall", Race::ORC, Class::WARRIOR).await, ResponseCode::CHAR_CREATE_SUCCESS);
+        assert_eq!(create(&options, "TEST", "Jaina", Race::HUMAN, Class::MAGE).await, ResponseCode::CHAR_CREATE_PVP_TEAMS_VIOLATION);
+        assert_eq!(create(&options, "TEST", "Rexxar", Race::ORC, Class::HUNTER).await, ResponseCode::CHAR_CREATE_SUCCESS);
+        assert_eq!(create(&options, "OTHER", "Jaina", Race::HUMAN, Class::MAGE).await, ResponseCode::CHAR_CREATE_SUCCESS);
+    }
+
+    // guid, name and appearance of CMSG_CHAR_CUSTOMIZE, race is appended for the faction change
+    fn appearance(guid: u64, name: &str, gender: u8) -> Vec<u8> {
+        let mut data = guid.to_le_bytes().to_vec();
+        data.extend(name.as_bytes());
+        data.extend([0, gender, 1, 2, 3, 4, 5]);
+        data
+    }
+
+    #[tokio::test]
+    async fn test_rename() {
+        let mut config = Config::default();
+        config.world.character_at_login = AtLoginFlags::RENAME;
+        let options = build_options(config);
+
+        create(&options, "TEST", "Thrall", Race::ORC, Class::WARRIOR).await;
+        create(&options, "TEST", "Rexxar", Race::ORC, Class::HUNTER).await;
+        let Characters(characters) = list(&options, "TEST").await;
+        assert_eq!(characters[0].flags, CharacterFlags::RENAME);
+        assert_eq!(characters[0].first_login, 1);
+
+        let rename = |name: &str| [characters[0].guid.to_le_bytes().to_vec(), name.as_bytes().to_vec(), vec![0]].concat();
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_RENAME, rename("rexxar")).await;
+        assert_eq!(body, vec![ResponseCode::CHAR_CREATE_NAME_IN_USE]);
+        let body = handle(&options, "OTHER", Opcode::CMSG_CHAR_RENAME, rename("Garrosh")).await;
+        assert_eq!(body, vec![ResponseCode::CHAR_CREATE_ERROR]);
+
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_RENAME, rename("gARROSH")).await;
+        assert_eq!(body[0], ResponseCode::RESPONSE_SUCCESS);
+        assert_eq!(body[9..], *b"Garrosh\0");
+        assert_eq!(list(&options, "TEST").await.0[0].flags, CharacterFlags::NONE);
+
+        // the flag is cleared after the rename
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_RENAME, rename("Thrall")).await;
+        assert_eq!(body, vec![ResponseCode::CHAR_CREATE_ERROR]);
+    }
+
+    #[tokio::test]
+    async fn test_customize() {
+        let options = build_options(Config::default());
+        create(&options, "TEST", "Thrall", Race::ORC, Class::WARRIOR).await;
+        let mut character = options.characters.find_by_name("Thrall", 1).unwrap();
+
+        let data = appearance(character.guid, "Thrall", 1);
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_CUSTOMIZE, data.clone()).await;
+        assert_eq!(body, vec![ResponseCode::CHAR_CREATE_ERROR]);
+
+        character.at_login |= AtLoginFlags::CUSTOMIZE;
+        options.characters.update(character.clone()).unwrap();
+        assert_eq!(list(&options, "TEST").await.0[0].customize_flags, CustomizeFlags::CUSTOMIZE);
+
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_CUSTOMIZE, data).await;
+        assert_eq!(body[0], ResponseCode::RESPONSE_SUCCESS);
+        // skin, face, hair style, hair color and facial hair are sent in another order
+        assert_eq!(body[16..], [1, 1, 5, 3, 2, 4]);
+
+        let character = options.characters.get(character.guid).unwrap();
+        assert_eq!((character.gender, character.hair_color, character.face), (1, 2, 5));
+        assert_eq!(character.at_login & AtLoginFlags::CUSTOMIZE, 0);
+    }
+
+    #[tokio::test]
+    async fn test_faction_and_race_change() {
+        let mut config = Config::default();
+        config.world.character_at_login = AtLoginFlags::CHANGE_FACTION | AtLoginFlags::CHANGE_RACE;
+        let options = build_options(config);
+        create(&options, "TEST", "Thrall", Race::ORC, Class::WARRIOR).await;
+        let guid = options.characters.find_by_name("Thrall", 1).unwrap().guid;
+
+        let change = |race: u8| [appearance(guid, "Thrall", 0), vec![race]].concat();
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_FACTION_CHANGE, change(Race::TROLL)).await;
+        assert_eq!(body, vec![ResponseCode::CHAR_CREATE_ERROR]);
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_FACTION_CHANGE, change(Race::BLOOD_ELF)).await;
+        assert_eq!(body, vec![ResponseCode::CHAR_CREATE_ERROR]);
+
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_FACTION_CHANGE, change(Race::HUMAN)).await;
+        assert_eq!(body[0], ResponseCode::RESPONSE_SUCCESS);
+        assert_eq!(body[body.len() - 1], Race::HUMAN);
+        let character = options.characters.get(guid).unwrap();
+        assert_eq!((character.race, character.map, character.zone), (Race::HUMAN, 0, 12));
+
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_RACE_CHANGE, change(Race::DWARF)).await;
+        assert_eq!(body[0], ResponseCode::RESPONSE_SUCCESS);
+        let character = options.characters.get(guid).unwrap();
+        assert_eq!((character.race, character.zone), (Race::DWARF, 12));
+        assert_eq!(character.at_login, AtLoginFlags::FIRST);
+
+        // the changed character itself does not count on pvp realms, other characters do
+        let mut config = Config::default();
+        config.realms[0].icon = RealmType::PVP;
+        config.world.character_at_login = AtLoginFlags::CHANGE_FACTION;
+        let options = build_options(config);
+        create(&options, "TEST", "Thrall", Race::ORC, Class::WARRIOR).await;
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_FACTION_CHANGE, change(Race::HUMAN)).await;
+        assert_eq!(body[0], ResponseCode::RESPONSE_SUCCESS);
+
+        create(&options, "TEST", "Varian", Race::HUMAN, Class::WARRIOR).await;
+        let guid = options.characters.find_by_name("Varian", 1).unwrap().guid;
+        let data = [appearance(guid, "Varian", 0), vec![Race::ORC]].concat();
+        let body = handle(&options, "TEST", Opcode::CMSG_CHAR_FACTION_CHANGE, data).await;
+        assert_eq!(body, vec![ResponseCode::CHAR_CREATE_PVP_TEAMS_VIOLATION]);
+        assert_eq!(options.characters.get(guid).unwrap().race, Race::HUMAN);
+    }
+}
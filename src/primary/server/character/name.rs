@@ -0,0 +1,62 @@
+use crate::primary::server::realm::types::ResponseCode;
+
+const MIN_NAME_LENGTH: usize = 2;
+const MAX_NAME_LENGTH: usize = 12;
+
+// returns the name with the first letter capitalized or CHAR_NAME_* code
+pub fn normalize_name(name: &str) -> Result<String, u8> {
+    let letters: Vec<char> = name.chars().collect();
+
+    if letters.is_empty() {
+        return Err(ResponseCode::CHAR_NAME_NO_NAME);
+    }
+
+    if letters.len() < MIN_NAME_LENGTH {
+        return Err(ResponseCode::CHAR_NAME_TOO_SHORT);
+    }
+
+    if letters.len() > MAX_NAME_LENGTH {
+        return Err(ResponseCode::CHAR_NAME_TOO_LONG);
+    }
+
+    if !letters.iter().all(|letter| letter.is_alphabetic()) {
+        return Err(ResponseCode::CHAR_NAME_INVALID_CHARACTER);
+    }
+
+    if letters.iter().any(|letter| letter.is_ascii()) && !letters.iter().all(|letter| letter.is_ascii()) {
+        return Err(ResponseCode::CHAR_NAME_MIXED_LANGUAGES);
+    }
+
+    let lowercase: Vec<char> = letters.iter().flat_map(|letter| letter.to_lowercase()).collect();
+    if lowercase.windows(3).any(|window| window[0] == window[1] && window[1] == window[2]) {
+        return Err(ResponseCode::CHAR_NAME_THREE_CONSECUTIVE);
+    }
+
+    let mut letters = lowercase.into_iter();
+    let first = letters.next().into_iter().flat_map(|letter| letter.to_uppercase());
+
+    Ok(first.chain(letters).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primary::server::character::name::normalize_name;
+    use crate::primary::server::realm::types::ResponseCode;
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("tHRALL"), Ok("Thrall".to_string()));
+        assert_eq!(normalize_name("жанна"), Ok("Жанна".to_string()));
+    }
+
+    #[test]
+    fn test_invalid_names() {
+        assert_eq!(normalize_name(""), Err(ResponseCode::CHAR_NAME_NO_NAME));
+        assert_eq!(normalize_name("a"), Err(ResponseCode::CHAR_NAME_TOO_SHORT));
+        assert_eq!(normalize_name("Abcdefghijklm"), Err(ResponseCode::CHAR_NAME_TOO_LONG));
+        assert_eq!(normalize_name("Thrall2"), Err(ResponseCode::CHAR_NAME_INVALID_CHARACTER));
+        assert_eq!(normalize_name("Jaina Proud"), Err(ResponseCode::CHAR_NAME_INVALID_CHARACTER));
+        assert_eq!(normalize_name("Thrallж"), Err(ResponseCode::CHAR_NAME_MIXED_LANGUAGES));
+        assert_eq!(normalize_name("Aaarthas"), Err(ResponseCode::CHAR_NAME_THREE_CONSECUTIVE));
+    }
+}
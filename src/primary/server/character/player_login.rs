@@ -0,0 +1,377 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tentacli::packet::idewave::WorldPacket;
+
+use crate::with_opcode;
+use crate::primary::objects::{UpdateFlags, UpdateObjectBuilder, WorldObject};
+use crate::primary::objects::fields::{PlayerFields, UnitFields};
+use crate::primary::server::character::{authorized, own_character};
+use crate::primary::server::character::types::{AtLoginFlags, Class, Power, Race};
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::server::realm::types::ResponseCode;
+use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::types::{Character, HandlerInput, HandlerOutput, HandlerResult, OutgoingPacket};
+use crate::primary::types::fields::spells::{ActionButton, ActionButtons, InitialSpells};
+use crate::primary::types::fields::movement::{MovementInfo, Position, Speeds};
+use crate::primary::types::fields::update_blocks::{LivingMovement, MovementBlock};
+
+// account data types stored per character, their times are sent on login
+const PER_CHARACTER_CACHE_MASK: u32 = 0xEA;
+// game minute per real second
+const GAME_SPEED: f32 = 0.01666667;
+const MAX_LEVEL: u32 = 80;
+// auto attack, dodge, unarmed and defense
+const INITIAL_SPELLS: [u32; 4] = [6603, 81, 203, 204];
+const AUTO_ATTACK: u32 = 6603;
+
+with_opcode! {
+    @world_opcode(Opcode::CMSG_PLAYER_LOGIN)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Income {
+        guid: u64,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_CHARACTER_LOGIN_FAILED)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct LoginFailedOutcome {
+        result: u8,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_LOGIN_VERIFY_WORLD)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct VerifyWorldOutcome {
+        map: u32,
+        x: f32,
+        y: f32,
+        z: f32,
+        orientation: f32,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_ACCOUNT_DATA_TIMES)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct AccountDataTimesOutcome {
+        time: u32,
+        activated: u8,
+        mask: u32,
+        // u32 time of every type in the mask, zero when the data was never saved
+        times: Vec<u8>,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_TUTORIAL_FLAGS)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct TutorialFlagsOutcome {
+        flags: [u8; 32],
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_BINDPOINTUPDATE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct BindPointOutcome {
+        x: f32,
+        y: f32,
+        z: f32,
+        map: u32,
+        zone: u32,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_INITIAL_SPELLS)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct InitialSpellsOutcome {
+        talent_spec: u8,
+        spells: InitialSpells,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_ACTION_BUTTONS)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct ActionButtonsOutcome {
+        // 0 sets the buttons, 1 sends them after the talent spec switch, 2 clears the action bars
+        state: u8,
+        buttons: ActionButtons,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_LOGIN_SETTIMESPEED)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct TimeSpeedOutcome {
+        time: u32,
+        speed: f32,
+        holiday_offset: u32,
+    }
+}
+
+pub struct Handler;
+#[async_trait]
+impl PacketHandler for Handler {
+    async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
+        let (Income { guid }, json_details) = Income::from_binary(&input.data)?;
+        input.json_details = Some(json_details);
+
+        let (account, realm_id) = authorized(input)?;
+
+        if input.session.lock().unwrap().character.is_some() {
+            return Self::reject(ResponseCode::CHAR_LOGIN_DUPLICATE_CHARACTER);
+        }
+
+        let Some(character) = own_character(input, guid, &account, realm_id) else {
+            return Self::reject(ResponseCode::CHAR_LOGIN_NO_CHARACTER);
+        };
+
+        // the client has to send CMSG_CHAR_RENAME first
+        if character.at_login & AtLoginFlags::RENAME != 0 {
+            return Self::reject(ResponseCode::CHAR_LOGIN_FAILED);
+        }
+
+        input.session.lock().unwrap().character = Some(guid);
+        input.options.characters.update(Character {
+            at_login: character.at_login & !AtLoginFlags::FIRST,
+            ..character.clone()
+        })?;
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let mut spells = vec![Race::language_spell(character.race)];
+        spells.extend(INITIAL_SPELLS);
+
+        Ok(vec![
+            HandlerOutput::Data(VerifyWorldOutcome {
+                map: character.map,
+                x: character.x,
+                y: character.y,
+                z: character.z,
+                orientation: character.orientation,
+            }.unpack()?),
+            HandlerOutput::Data(AccountDataTimesOutcome {
+                time: now.as_secs() as u32,
+                activated: 1,
+                mask: PER_CHARACTER_CACHE_MASK,
+                times: vec![0; 4 * PER_CHARACTER_CACHE_MASK.count_ones() as usize],
+            }.unpack()?),
+            // every tutorial is marked as read
+            HandlerOutput::Data(TutorialFlagsOutcome {
+                flags: [0xFF; 32],
+            }.unpack()?),
+            HandlerOutput::Data(BindPointOutcome {
+                x: character.x,
+                y: character.y,
+                z: character.z,
+                map: character.map,
+                zone: character.zone,
+            }.unpack()?),
+            HandlerOutput::Data(InitialSpellsOutcome {
+                talent_spec: 0,
+                spells: InitialSpells { spells, cooldowns: vec![] },
+            }.unpack()?),
+            HandlerOutput::Data(ActionButtonsOutcome {
+                state: 0,
+                buttons: ActionButtons(vec![ActionButton { action: AUTO_ATTACK, action_type: 0 }]),
+            }.unpack()?),
+            HandlerOutput::Data(TimeSpeedOutcome {
+                time: Self::packed_time(now.as_secs()),
+                speed: GAME_SPEED,
+                holiday_offset: 0,
+            }.unpack()?),
+            HandlerOutput::Data(Self::create_player(&character, now.as_millis() as u32)?),
+        ])
+    }
+}
+
+impl Handler {
+    fn reject(result: u8) -> HandlerResult {
+        Ok(vec![HandlerOutput::Data(LoginFailedOutcome { result }.unpack()?)])
+    }
+
+    // creates the object of the player itself, the client enters the world after receiving it
+    fn create_player(character: &Character, time: u32) -> anyhow::Result<OutgoingPacket> {
+        let mut player = WorldObject::player(character.guid);
+
+        let power = Class::power_type(character.class);
+        let (current_power, max_power) = match power {
+            Power::RAGE | Power::RUNIC_POWER => (0, 1000),
+            _ => (100, 100),
+        };
+        let display_id = Race::display_id(character.race, character.gender);
+
+        player.set_bytes(UnitFields::BYTES_0, [character.race, character.class, character.gender, power]);
+        player.set_u32(UnitFields::HEALTH, 100);
+        player.set_u32(UnitFields::POWER1 + power as u16, current_power);
+        player.set_u32(UnitFields::MAX_HEALTH, 100);
+        player.set_u32(UnitFields::MAX_POWER1 + power as u16, max_power);
+        player.set_u32(UnitFields::LEVEL, character.level as u32);
+        player.set_u32(UnitFields::FACTION_TEMPLATE, Race::faction_template(character.race));
+        // pvp attackable
+        player.set_u32(UnitFields::FLAGS, 0x08);
+        player.set_f32(UnitFields::BOUNDING_RADIUS, 0.389);
+        player.set_f32(UnitFields::COMBAT_REACH, 1.5);
+        player.set_u32(UnitFields::DISPLAY_ID, display_id);
+        player.set_u32(UnitFields::NATIVE_DISPLAY_ID, display_id);
+        player.set_f32(UnitFields::MOD_CAST_SPEED, 1.0);
+        player.set_bytes(
+            PlayerFields::BYTES,
+            [character.skin, character.face, character.hair_style, character.hair_color],
+        );
+        // rested state is normal
+        player.set_bytes(PlayerFields::BYTES_2, [character.facial_hair, 0, 0, 2]);
+        player.set_u32(PlayerFields::BYTES_3, character.gender as u32);
+        player.set_u32(PlayerFields::MAX_LEVEL, MAX_LEVEL);
+
+        let movement = MovementBlock {
+            update_flags: UpdateFlags::SELF | UpdateFlags::LIVING,
+            living: Some(LivingMovement {
+                movement: MovementInfo {
+                    time,
+                    position: Position {
+                        x: character.x,
+                        y: character.y,
+                        z: character.z,
+                        orientation: character.orientation,
+                    },
+                    ..MovementInfo::default()
+                },
+                speeds: Speeds::default(),
+            }),
+            ..MovementBlock::default()
+        };
+
+        let mut packets = UpdateObjectBuilder::new().spawn(&mut player, movement).build()?;
+
+        Ok(packets.remove(0))
+    }
+
+    // date in the client format: minutes, hours, weekday, day, month and years since 2000
+    fn packed_time(unix: u64) -> u32 {
+        let days = (unix / 86400) as i64;
+        let minutes = (unix % 86400 / 60) as u32;
+
+        // civil date from days since 1970-01-01
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let day_of_era = z - era * 146097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+        let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u32;
+        let year = (year_of_era + era * 400 + (month <= 2) as i64) as u32;
+        // 1970-01-01 was thursday, sunday is zero
+        let weekday = (days + 4).rem_euclid(7) as u32;
+
+        ((year - 2000) << 24) | ((month - 1) << 20) | ((day - 1) << 14) | (weekday << 11) | ((minutes / 60) << 6) | (minutes % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::primary::config::Config;
+    use crate::primary::server::character::player_login::Handler;
+    use crate::primary::server::character::types::{AtLoginFlags, Class, Race};
+    use crate::primary::server::realm::types::ResponseCode;
+    use crate::primary::server::Opcode;
+    use crate::primary::traits::server::{handle_packets, test_input, RunOptions};
+    use crate::primary::types::{Character, HandlerInput, Session};
+
+    fn build_input(options: &Arc<RunOptions>, account: &str, guid: u64) -> HandlerInput {
+        let session = Session { account: Some(account.to_string()), realm_id: 1, ..Session::new() };
+
+        test_input(options, session, Opcode::CMSG_PLAYER_LOGIN, guid.to_le_bytes().to_vec())
+    }
+
+    // opcodes and bodies of all packets, the header is skipped
+    async fn handle(input: &mut HandlerInput) -> Vec<(u32, Vec<u8>)> {
+        handle_packets(&mut Handler, input).await
+            .into_iter()
+            .map(|packet| (packet.opcode, packet.data[6..].to_vec()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_player_login() {
+        let options = Arc::new(RunOptions::for_tests(Config::default()));
+        let character = options.characters.create(Character {
+            account: "TEST".to_string(),
+            realm_id: 1,
+            name: "Thrall".to_string(),
+            race: Race::ORC,
+            class: Class::WARRIOR,
+            level: 1,
+            zone: 14,
+            map: 1,
+            x: -618.518,
+            y: -4251.67,
+            z: 38.718,
+            orientation: 1.5,
+            at_login: AtLoginFlags::FIRST | AtLoginFlags::RENAME,
+            ..Character::default()
+        }).unwrap();
+
+        let packets = handle(&mut build_input(&options, "OTHER", character.guid)).await;
+        assert_eq!(packets, vec![(Opcode::SMSG_CHARACTER_LOGIN_FAILED as u32, vec![ResponseCode::CHAR_LOGIN_NO_CHARACTER])]);
+        assert_eq!(options.characters.get(character.guid).unwrap().at_login, AtLoginFlags::FIRST | AtLoginFlags::RENAME);
+
+        let mut input = build_input(&options, "TEST", character.guid);
+        let packets = handle(&mut input).await;
+        assert_eq!(packets, vec![(Opcode::SMSG_CHARACTER_LOGIN_FAILED as u32, vec![ResponseCode::CHAR_LOGIN_FAILED])]);
+        assert_eq!(input.session.lock().unwrap().character, None);
+
+        options.characters.update(Character { at_login: AtLoginFlags::FIRST, ..character.clone() }).unwrap();
+        let packets = handle(&mut input).await;
+        let opcodes: Vec<u32> = packets.iter().map(|(opcode, _)| *opcode).collect();
+        assert_eq!(opcodes, [
+            Opcode::SMSG_LOGIN_VERIFY_WORLD,
+            Opcode::SMSG_ACCOUNT_DATA_TIMES,
+            Opcode::SMSG_TUTORIAL_FLAGS,
+            Opcode::SMSG_BINDPOINTUPDATE,
+            Opcode::SMSG_INITIAL_SPELLS,
+            Opcode::SMSG_ACTION_BUTTONS,
+            Opcode::SMSG_LOGIN_SETTIMESPEED,
+            Opcode::SMSG_UPDATE_OBJECT,
+        ].map(|opcode| opcode as u32));
+
+        // map, x, y, z and orientation
+        let verify_world = [
+            1u32.to_le_bytes(),
+            (-618.518f32).to_le_bytes(),
+            (-4251.67f32).to_le_bytes(),
+            38.718f32.to_le_bytes(),
+            1.5f32.to_le_bytes(),
+        ].concat();
+        assert_eq!(packets[0].1, verify_world);
+
+        // x, y, z, map and zone
+        let bind_point = [
+            (-618.518f32).to_le_bytes(),
+            (-4251.67f32).to_le_bytes(),
+            38.718f32.to_le_bytes(),
+            1u32.to_le_bytes(),
+            14u32.to_le_bytes(),
+        ].concat();
+        assert_eq!(packets[3].1, bind_point);
+
+        assert_eq!(input.session.lock().unwrap().character, Some(character.guid));
+        assert_eq!(options.characters.get(character.guid).unwrap().at_login, AtLoginFlags::NONE);
+
+        let packets = handle(&mut input).await;
+        assert_eq!(packets, vec![(Opcode::SMSG_CHARACTER_LOGIN_FAILED as u32, vec![ResponseCode::CHAR_LOGIN_DUPLICATE_CHARACTER])]);
+    }
+
+    #[test]
+    fn test_packed_time() {
+        // 2024-03-15 12:30:45, friday
+        assert_eq!(Handler::packed_time(1710505845), 24 << 24 | 2 << 20 | 14 << 14 | 5 << 11 | 12 << 6 | 30);
+    }
+}
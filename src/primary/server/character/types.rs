@@ -0,0 +1,173 @@
+#[non_exhaustive]
+pub struct Race;
+
+#[allow(dead_code)]
+impl Race {
+    pub const HUMAN: u8     = 1;
+    pub const ORC: u8       = 2;
+    pub const DWARF: u8     = 3;
+    pub const NIGHT_ELF: u8 = 4;
+    pub const UNDEAD: u8    = 5;
+    pub const TAUREN: u8    = 6;
+    pub const GNOME: u8     = 7;
+    pub const TROLL: u8     = 8;
+    pub const BLOOD_ELF: u8 = 10;
+    pub const DRAENEI: u8   = 11;
+
+    // classes the race can be created with, empty for unknown races
+    pub fn classes(race: u8) -> &'static [u8] {
+        match race {
+            Self::HUMAN => &[Class::WARRIOR, Class::PALADIN, Class::ROGUE, Class::PRIEST, Class::DEATH_KNIGHT, Class::MAGE, Class::WARLOCK],
+            Self::ORC => &[Class::WARRIOR, Class::HUNTER, Class::ROGUE, Class::DEATH_KNIGHT, Class::SHAMAN, Class::WARLOCK],
+            Self::DWARF => &[Class::WARRIOR, Class::PALADIN, Class::HUNTER, Class::ROGUE, Class::PRIEST, Class::DEATH_KNIGHT],
+            Self::NIGHT_ELF => &[Class::WARRIOR, Class::HUNTER, Class::ROGUE, Class::PRIEST, Class::DEATH_KNIGHT, Class::DRUID],
+            Self::UNDEAD => &[Class::WARRIOR, Class::ROGUE, Class::PRIEST, Class::DEATH_KNIGHT, Class::MAGE, Class::WARLOCK],
+            Self::TAUREN => &[Class::WARRIOR, Class::HUNTER, Class::DEATH_KNIGHT, Class::SHAMAN, Class::DRUID],
+            Self::GNOME => &[Class::WARRIOR, Class::ROGUE, Class::DEATH_KNIGHT, Class::MAGE, Class::WARLOCK],
+            Self::TROLL => &[Class::WARRIOR, Class::HUNTER, Class::ROGUE, Class::PRIEST, Class::DEATH_KNIGHT, Class::SHAMAN, Class::MAGE],
+            Self::BLOOD_ELF => &[Class::PALADIN, Class::HUNTER, Class::ROGUE, Class::PRIEST, Class::DEATH_KNIGHT, Class::MAGE, Class::WARLOCK],
+            Self::DRAENEI => &[Class::WARRIOR, Class::PALADIN, Class::HUNTER, Class::PRIEST, Class::DEATH_KNIGHT, Class::SHAMAN, Class::MAGE],
+            _ => &[],
+        }
+    }
+
+    pub fn is_alliance(race: u8) -> bool {
+        matches!(race, Self::HUMAN | Self::DWARF | Self::NIGHT_ELF | Self::GNOME | Self::DRAENEI)
+    }
+
+    // from ChrRaces.dbc
+    pub fn faction_template(race: u8) -> u32 {
+        match race {
+            Self::ORC => 2,
+            Self::DWARF => 3,
+            Self::NIGHT_ELF => 4,
+            Self::UNDEAD => 5,
+            Self::TAUREN => 6,
+            Self::GNOME => 115,
+            Self::TROLL => 116,
+            Self::BLOOD_ELF => 1610,
+            Self::DRAENEI => 1629,
+            _ => 1,
+        }
+    }
+
+    // male model from ChrRaces.dbc, female one follows it except for blood elves
+    pub fn display_id(race: u8, gender: u8) -> u32 {
+        let (male, female) = match race {
+            Self::ORC => (51, 52),
+            Self::DWARF => (53, 54),
+            Self::NIGHT_ELF => (55, 56),
+            Self::UNDEAD => (57, 58),
+            Self::TAUREN => (59, 60),
+            Self::GNOME => (1563, 1564),
+            Self::TROLL => (1478, 1479),
+            Self::BLOOD_ELF => (15476, 15475),
+            Self::DRAENEI => (16125, 16126),
+            _ => (49, 50),
+        };
+
+        match gender {
+            Gender::FEMALE => female,
+            _ => male,
+        }
+    }
+
+    // racial language learned by every character
+    pub fn language_spell(race: u8) -> u32 {
+        match Self::is_alliance(race) {
+            true => 668,
+            false => 669,
+        }
+    }
+}
+
+#[non_exhaustive]
+pub struct Class;
+
+#[allow(dead_code)]
+impl Class {
+    pub const WARRIOR: u8      = 1;
+    pub const PALADIN: u8      = 2;
+    pub const HUNTER: u8       = 3;
+    pub const ROGUE: u8        = 4;
+    pub const PRIEST: u8       = 5;
+    pub const DEATH_KNIGHT: u8 = 6;
+    pub const SHAMAN: u8       = 7;
+    pub const MAGE: u8         = 8;
+    pub const WARLOCK: u8      = 9;
+    pub const DRUID: u8        = 11;
+
+    pub fn power_type(class: u8) -> u8 {
+        match class {
+            Self::WARRIOR => Power::RAGE,
+            Self::ROGUE => Power::ENERGY,
+            Self::DEATH_KNIGHT => Power::RUNIC_POWER,
+            _ => Power::MANA,
+        }
+    }
+}
+
+#[non_exhaustive]
+pub struct Power;
+
+#[allow(dead_code)]
+impl Power {
+    pub const MANA: u8        = 0;
+    pub const RAGE: u8        = 1;
+    pub const FOCUS: u8       = 2;
+    pub const ENERGY: u8      = 3;
+    pub const HAPPINESS: u8   = 4;
+    pub const RUNES: u8       = 5;
+    pub const RUNIC_POWER: u8 = 6;
+}
+
+#[non_exhaustive]
+pub struct Gender;
+
+#[allow(dead_code)]
+impl Gender {
+    pub const MALE: u8   = 0;
+    pub const FEMALE: u8 = 1;
+}
+
+#[non_exhaustive]
+pub struct AtLoginFlags;
+
+#[allow(dead_code)]
+impl AtLoginFlags {
+    pub const NONE: u16              = 0x00;
+    pub const RENAME: u16            = 0x01;
+    pub const RESET_SPELLS: u16      = 0x02;
+    pub const RESET_TALENTS: u16     = 0x04;
+    pub const CUSTOMIZE: u16         = 0x08;
+    pub const RESET_PET_TALENTS: u16 = 0x10;
+    pub const FIRST: u16             = 0x20;
+    pub const CHANGE_FACTION: u16    = 0x40;
+    pub const CHANGE_RACE: u16       = 0x80;
+}
+
+#[non_exhaustive]
+pub struct CharacterFlags;
+
+#[allow(dead_code)]
+impl CharacterFlags {
+    pub const NONE: u32                = 0x00000000;
+    pub const LOCKED_FOR_TRANSFER: u32 = 0x00000004;
+    pub const HIDE_HELM: u32           = 0x00000400;
+    pub const HIDE_CLOAK: u32          = 0x00000800;
+    pub const GHOST: u32               = 0x00002000;
+    pub const RENAME: u32              = 0x00004000;
+    pub const LOCKED_BY_BILLING: u32   = 0x01000000;
+    pub const DECLINED: u32            = 0x02000000;
+}
+
+#[non_exhaustive]
+pub struct CustomizeFlags;
+
+#[allow(dead_code)]
+impl CustomizeFlags {
+    pub const NONE: u32      = 0x00000000;
+    pub const CUSTOMIZE: u32 = 0x00000001;
+    pub const FACTION: u32   = 0x00010000;
+    pub const RACE: u32      = 0x00100000;
+}
@@ -1,91 +1,136 @@
-use std::collections::BTreeMap;
-use std::sync::{Arc};
+use std::sync::{Arc, Mutex as SyncMutex};
 use async_trait::async_trait;
-use tokio::io::{AsyncWriteExt};
-use tokio::net::TcpStream;
 
 mod opcodes;
 mod auth;
-
-use crate::primary::server::auth::{auth_challenge, AuthProcessor};
+mod character;
+mod realm;
+mod sessions;
+mod realm_status;
+mod shutdown;
+
+use crate::primary::capture::ServerKind;
+use crate::primary::codecs::{LoginCodec, WorldCodec};
+use crate::primary::config::RealmConfig;
+use crate::primary::server::auth::AuthProcessor;
+use crate::primary::server::character::CharacterProcessor;
+use crate::primary::server::realm::{auth_challenge, shutdown_notice, RealmProcessor};
+use crate::primary::traits::packet_codec::PacketCodec;
 use crate::primary::traits::processor::Processor;
 use crate::primary::traits::server::{RunOptions, Server};
-use crate::primary::types::{HandlerInput, ProcessorFunction};
+use crate::primary::types::{ProcessorFunction, Session};
 
-const HOST: &str = "127.0.0.1";
-const LOGIN_PORT: u16 = 3724;
-pub const WORLD_PORT: u16 = 8999;
+pub use auth::types::RealmFlags;
+pub use opcodes::Opcode;
+pub use realm_status::RealmStatus;
+pub use sessions::{OnlineCounter, OnlineGuard, SessionKey, Sessions};
+pub use shutdown::Shutdown;
 
-type SessionKey = Vec<u8>;
-type Sessions = BTreeMap<String, Option<SessionKey>>;
+#[derive(Default)]
+pub struct LoginServer;
 
-pub struct LoginServer {}
+impl LoginServer {
+    pub fn new() -> Self {
+        Self
+    }
+}
 
 #[async_trait]
 impl Server for LoginServer {
-    fn new() -> Self {
-        Self {}
-    }
-
-    fn generate_input(packet: &[u8], options: &RunOptions) -> HandlerInput {
-        HandlerInput {
-            data: packet[1..].to_vec(),
-            opcode: packet[0] as u16,
-            srp: Arc::clone(&options.srp),
-        }
+    fn codec() -> Box<dyn PacketCodec> {
+        Box::new(LoginCodec)
     }
 
     fn get_processors() -> Vec<ProcessorFunction> {
         vec![Box::new(AuthProcessor::get_handlers)]
     }
 
-    fn host<'a>() -> &'a str {
-        HOST
+    fn opcode_name(opcode: u16) -> String {
+        Opcode::get_login_opcode_name(opcode as u32).unwrap_or_else(|| format!("UNKNOWN_LOGIN_OPCODE_{}", opcode))
     }
 
-    fn port() -> u16 {
-        LOGIN_PORT
+    fn server_kind() -> ServerKind {
+        ServerKind::Login
     }
 
-    fn server_name<'a>() -> &'a str {
-        "Login Server"
+    fn host(&self, options: &RunOptions) -> String {
+        options.config.login.host.clone()
+    }
+
+    fn port(&self, options: &RunOptions) -> u16 {
+        options.config.login.port
+    }
+
+    fn server_name(&self) -> String {
+        "Login Server".to_string()
     }
 }
 
-pub struct WorldServer {}
+// serves single realm from config.realms
+pub struct WorldServer {
+    realm: RealmConfig,
+}
+
+impl WorldServer {
+    pub fn new(realm: RealmConfig) -> Self {
+        Self { realm }
+    }
+}
 
 #[async_trait]
 impl Server for WorldServer {
-    fn new() -> Self {
-        Self {}
+    async fn init(&self, session: &Arc<SyncMutex<Session>>) {
+        session.lock().unwrap().realm_id = self.realm.id;
+        let packet = auth_challenge(session).await.unwrap();
+        session.lock().unwrap().send(packet).unwrap();
+    }
+
+    fn codec() -> Box<dyn PacketCodec> {
+        Box::new(WorldCodec::default())
+    }
+
+    fn get_processors() -> Vec<ProcessorFunction> {
+        vec![
+            Box::new(RealmProcessor::get_handlers),
+            Box::new(CharacterProcessor::get_handlers),
+        ]
     }
 
-    async fn init(socket: &mut TcpStream) {
-        let packet = auth_challenge().await.unwrap();
-        socket.write_all(&packet).await.unwrap();
+    fn opcode_name(opcode: u16) -> String {
+        Opcode::get_world_opcode_name(opcode as u32).unwrap_or_else(|| format!("UNKNOWN_WORLD_OPCODE_{}", opcode))
     }
 
-    fn generate_input(_packet: &[u8], options: &RunOptions) -> HandlerInput {
-        HandlerInput {
-            data: vec![],
-            opcode: 0,
-            srp: Arc::clone(&options.srp),
+    fn server_kind() -> ServerKind {
+        ServerKind::World
+    }
+
+    fn notify_shutdown(&self, session: &Arc<SyncMutex<Session>>, options: &RunOptions) {
+        let session = session.lock().unwrap();
+        // only clients which passed CMSG_AUTH_SESSION can decrypt the packet
+        if session.header_crypt.is_some() {
+            if let Ok(packet) = shutdown_notice(options.config.drain_timeout) {
+                let _ = session.send(packet);
+            }
         }
     }
 
-    fn get_processors() -> Vec<ProcessorFunction> {
-        vec![]
+    fn started(&self, options: &RunOptions) {
+        options.realm_status.set_listening(self.realm.id, true);
     }
 
-    fn host<'a>() -> &'a str {
-        HOST
+    fn stopped(&self, options: &RunOptions) {
+        options.realm_status.set_listening(self.realm.id, false);
     }
 
-    fn port() -> u16 {
-        WORLD_PORT
+    fn host(&self, options: &RunOptions) -> String {
+        options.config.world.host.clone()
     }
 
-    fn server_name<'a>() -> &'a str {
-        "World Server"
+    fn port(&self, options: &RunOptions) -> u16 {
+        options.config.realm_port(&self.realm)
     }
-}
\ No newline at end of file
+
+    fn server_name(&self) -> String {
+        format!("World Server #{} {}", self.realm.id, self.realm.name)
+    }
+}
@@ -4,6 +4,8 @@ opcodes! {
     pub struct Opcode {
         pub const LOGIN_CHALLENGE: u8 = 0;
         pub const LOGIN_PROOF: u8 = 1;
+        pub const RECONNECT_CHALLENGE: u8 = 2;
+        pub const RECONNECT_PROOF: u8 = 3;
         pub const REALM_LIST: u8 = 16;
 
         pub const MSG_NULL_ACTION: u16 = 0;
@@ -1322,7 +1324,7 @@ opcodes! {
 
 #[cfg(test)]
 mod tests {
-    use crate::primary::server::Opcode;
+    use crate::primary::server::opcodes::Opcode;
 
     fn get_type_of<T>(_: &T) -> &str {
         std::any::type_name::<T>()
@@ -1344,6 +1346,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_overlapping_opcode_name() {
+        assert_eq!(
+            Opcode::get_login_opcode_name(Opcode::REALM_LIST as u32),
+            Some("REALM_LIST".to_string()),
+        );
+        assert_eq!(
+            Opcode::get_world_opcode_name(Opcode::CMSG_LEARN_SPELL),
+            Some("CMSG_LEARN_SPELL".to_string()),
+        );
+        assert_eq!(Opcode::get_login_opcode_name(Opcode::CMSG_CHAR_ENUM), None);
+    }
+
     #[test]
     fn test_opcode_value() {
         assert_eq!(Opcode::LOGIN_PROOF, 1);
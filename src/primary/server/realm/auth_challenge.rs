@@ -1,8 +1,10 @@
+use std::sync::{Arc, Mutex as SyncMutex};
 use anyhow::{Result as AnyResult};
 use serde::{Deserialize, Serialize};
 use tentacli::packet::idewave::WorldPacket;
 
 use crate::primary::server::opcodes::Opcode;
+use crate::primary::types::{OutgoingPacket, Session};
 use crate::with_opcode;
 
 with_opcode! {
@@ -15,12 +17,15 @@ with_opcode! {
     }
 }
 
-pub async fn handle() -> AnyResult<Vec<u8>> {
+pub async fn handle(session: &Arc<SyncMutex<Session>>) -> AnyResult<OutgoingPacket> {
+    let server_seed = rand::random();
+    session.lock().unwrap().server_seed = server_seed;
+
     let packet = Outcome {
         unknown: 0,
-        server_seed: rand::random(),
+        server_seed,
         seed: rand::random(),
-    }.to_binary()?;
+    }.unpack()?;
 
     Ok(packet)
-}
\ No newline at end of file
+}
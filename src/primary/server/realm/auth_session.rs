@@ -0,0 +1,234 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use tentacli::packet::custom_fields::TerminatedString;
+use tentacli::packet::idewave::WorldPacket;
+
+use crate::with_opcode;
+use crate::primary::crypto::header_crypt::HeaderCrypt;
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::server::realm::types::{Expansion, ResponseCode};
+use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::types::{HandlerInput, HandlerOutput, HandlerResult};
+use crate::primary::types::fields::addon_info::AddonInfo;
+
+with_opcode! {
+    @world_opcode(Opcode::CMSG_AUTH_SESSION)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Income {
+        build: u32,
+        login_server_id: u32,
+        account: TerminatedString,
+        login_server_type: u32,
+        client_seed: u32,
+        region_id: u32,
+        battlegroup_id: u32,
+        realm_id: u32,
+        dos_response: u64,
+        digest: [u8; 20],
+        addon_info: AddonInfo,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_AUTH_RESPONSE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct Outcome {
+        code: u8,
+        billing_time_remaining: u32,
+        billing_plan_flags: u8,
+        billing_time_rested: u32,
+        expansion: u8,
+    }
+}
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_AUTH_RESPONSE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+    struct ErrorOutcome {
+        code: u8,
+    }
+}
+
+pub struct Handler;
+#[async_trait]
+impl PacketHandler for Handler {
+    async fn handle(&mut self, input: &mut HandlerInput) -> HandlerResult {
+        let mut response = Vec::new();
+        let (Income { build, account, client_seed, digest, .. }, json_details) = Income::from_binary(&input.data)?;
+        input.json_details = Some(json_details);
+
+        let options = &input.options;
+        if options.shutdown.is_triggered() {
+            return Self::reject(ResponseCode::AUTH_SERVER_SHUTTING_DOWN);
+        }
+
+        if !options.config.login.allowed_builds.iter().any(|allowed| *allowed as u32 == build) {
+            return Self::reject(ResponseCode::AUTH_VERSION_MISMATCH);
+        }
+
+        let account = match options.accounts.get(&account.0) {
+            Some(account) if account.banned => {
+                return Self::reject(ResponseCode::AUTH_BANNED);
+            },
+            Some(account) if account.suspended => {
+                return Self::reject(ResponseCode::AUTH_SUSPENDED);
+            },
+            Some(account) => account,
+            None => {
+                return Self::reject(ResponseCode::AUTH_UNKNOWN_ACCOUNT);
+            },
+        };
+
+        let Some(session_key) = options.sessions.get(&account.name) else {
+            return Self::reject(ResponseCode::AUTH_SESSION_EXPIRED);
+        };
+
+        // the client decrypts headers right after CMSG_AUTH_SESSION, so even the rejections are encrypted
+        input.session.lock().unwrap().header_crypt = Some(HeaderCrypt::new(&session_key));
+
+        let server_seed = input.session.lock().unwrap().server_seed;
+        let server_digest = Sha1::new()
+            .chain(account.name.as_bytes())
+            .chain([0u8; 4])
+            .chain(client_seed.to_le_bytes())
+            .chain(server_seed.to_le_bytes())
+            .chain(&session_key)
+            .finalize();
+
+        if server_digest.as_slice() != digest {
+            return Self::reject(ResponseCode::AUTH_FAILED);
+        }
+
+        // there is no login queue, accounts over the limit are rejected
+        let realm_id = input.session.lock().unwrap().realm_id;
+        let online_counter = options.realm_status.online(realm_id);
+        let player_limit = options.config.world.player_limit;
+        if player_limit > 0 && online_counter.count() >= player_limit {
+            return Self::reject(ResponseCode::AUTH_REJECT);
+        }
+
+        {
+            let mut session = input.session.lock().unwrap();
+            session.online = Some(online_counter.enter(&account.name));
+            session.account = Some(account.name);
+            session.session_key = Some(session_key);
+        }
+
+        response.push(HandlerOutput::Data(Outcome {
+            code: ResponseCode::AUTH_OK,
+            billing_time_remaining: 0,
+            billing_plan_flags: 0,
+            billing_time_rested: 0,
+            expansion: Expansion::WOTLK,
+        }.unpack()?));
+
+        Ok(response)
+    }
+}
+
+impl Handler {
+    fn reject(code: u8) -> HandlerResult {
+        Ok(vec![HandlerOutput::Data(ErrorOutcome { code }.unpack()?)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use sha1::{Digest, Sha1};
+
+    use crate::primary::accounts::InMemoryAccountRepository;
+    use crate::primary::config::Config;
+    use crate::primary::server::realm::auth_session::Handler;
+    use crate::primary::server::realm::types::ResponseCode;
+    use crate::primary::server::{Opcode, Sessions};
+    use crate::primary::traits::server::{handle_single, test_input, RunOptions};
+    use crate::primary::types::{Account, HandlerInput, Session};
+
+    fn build_input(session_key: &[u8], client_key: &[u8], player_limit: usize) -> HandlerInput {
+        let server_seed: u32 = rand::random();
+        let client_seed: u32 = rand::random();
+
+        let accounts = InMemoryAccountRepository::new();
+        accounts.insert(Account::new("test", "test"));
+
+        let sessions = Sessions::new(Duration::from_secs(60));
+        sessions.insert("TEST", session_key.to_vec());
+
+        let digest = Sha1::new()
+            .chain("TEST")
+            .chain([0u8; 4])
+            .chain(client_seed.to_le_bytes())
+            .chain(server_seed.to_le_bytes())
+            .chain(client_key)
+            .finalize();
+
+        let mut data = Vec::new();
+        data.extend(12340u32.to_le_bytes());
+        data.extend(0u32.to_le_bytes());
+        data.extend(b"TEST\0");
+        data.extend(0u32.to_le_bytes());
+        data.extend(client_seed.to_le_bytes());
+        data.extend([0u8; 12]);
+        data.extend(0u64.to_le_bytes());
+        data.extend(digest);
+        data.extend(0u32.to_le_bytes());
+
+        let mut config = Config::default();
+        config.world.player_limit = player_limit;
+
+        let options = Arc::new(RunOptions {
+            accounts: Arc::new(accounts),
+            sessions: Arc::new(sessions),
+            ..RunOptions::for_tests(config)
+        });
+        let session = Session { server_seed, realm_id: 1, ..Session::new() };
+
+        test_input(&options, session, Opcode::CMSG_AUTH_SESSION, data)
+    }
+
+    // returns packet body, the header is skipped
+    async fn handle(input: &mut HandlerInput) -> Vec<u8> {
+        handle_single(&mut Handler, input).await[6..].to_vec()
+    }
+
+    #[tokio::test]
+    async fn test_valid_digest() {
+        let session_key = rand::random::<[u8; 32]>();
+        let mut input = build_input(&session_key, &session_key, 0);
+
+        assert_eq!(handle(&mut input).await[0], ResponseCode::AUTH_OK);
+        assert_eq!(input.options.realm_status.online(1).count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_digest() {
+        let session_key = rand::random::<[u8; 32]>();
+        let mut input = build_input(&session_key, &[0; 40], 0);
+
+        assert_eq!(handle(&mut input).await, vec![ResponseCode::AUTH_FAILED]);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_when_limit_reached() {
+        let session_key = rand::random::<[u8; 32]>();
+        let mut input = build_input(&session_key, &session_key, 1);
+        let _guard = input.options.realm_status.online(1).enter("OTHER");
+
+        assert_eq!(handle(&mut input).await, vec![ResponseCode::AUTH_REJECT]);
+        let session = input.session.lock().unwrap();
+        assert!(session.header_crypt.is_some());
+        assert!(session.account.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rejected_on_shutdown() {
+        let session_key = rand::random::<[u8; 32]>();
+        let mut input = build_input(&session_key, &session_key, 0);
+        input.options.shutdown.trigger();
+
+        assert_eq!(handle(&mut input).await, vec![ResponseCode::AUTH_SERVER_SHUTTING_DOWN]);
+    }
+}
@@ -0,0 +1,27 @@
+mod auth_challenge;
+mod auth_session;
+mod server_message;
+pub(super) mod types;
+
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::traits::processor::Processor;
+use crate::primary::types::{HandlerInput, ProcessorResult};
+pub use auth_challenge::handle as auth_challenge;
+pub use server_message::shutdown_notice;
+
+pub struct RealmProcessor;
+
+impl Processor for RealmProcessor {
+    fn get_handlers(input: &mut HandlerInput) -> ProcessorResult {
+        let opcode = input.opcode as u32;
+
+        let handlers: ProcessorResult = match opcode {
+            Opcode::CMSG_AUTH_SESSION => {
+                vec![Box::new(auth_session::Handler)]
+            },
+            _ => vec![],
+        };
+
+        handlers
+    }
+}
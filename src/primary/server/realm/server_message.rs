@@ -0,0 +1,33 @@
+use anyhow::{Result as AnyResult};
+use serde::{Deserialize, Serialize};
+use tentacli::packet::custom_fields::TerminatedString;
+use tentacli::packet::idewave::WorldPacket;
+
+use crate::primary::server::opcodes::Opcode;
+use crate::primary::server::realm::types::ServerMessageType;
+use crate::primary::types::OutgoingPacket;
+use crate::with_opcode;
+
+with_opcode! {
+    @world_opcode(Opcode::SMSG_SERVER_MESSAGE)
+    #[derive(WorldPacket, Serialize, Deserialize, Debug, Default)]
+    struct Outcome {
+        message_type: u32,
+        text: TerminatedString,
+    }
+}
+
+// client shows "Shutdown in <text>" in the chat and in the center of the screen
+pub fn shutdown_notice(seconds: u64) -> AnyResult<OutgoingPacket> {
+    let text = match seconds {
+        0..=59 => format!("{} sec", seconds),
+        _ => format!("{} min {} sec", seconds / 60, seconds % 60),
+    };
+
+    let packet = Outcome {
+        message_type: ServerMessageType::SHUTDOWN_TIME,
+        text: TerminatedString::from(text),
+    }.unpack()?;
+
+    Ok(packet)
+}
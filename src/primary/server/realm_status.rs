@@ -0,0 +1,61 @@
+use std::collections::BTreeMap;
+use std::sync::{Mutex as SyncMutex};
+
+use crate::primary::server::OnlineCounter;
+
+#[derive(Debug, Default)]
+struct RealmState {
+    online: OnlineCounter,
+    listening: bool,
+}
+
+// live state of every realm, world servers update it and login server reads it for REALM_LIST
+#[derive(Debug, Default)]
+pub struct RealmStatus {
+    realms: SyncMutex<BTreeMap<u8, RealmState>>,
+}
+
+impl RealmStatus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // accounts in the world of the realm, counter is shared with all callers
+    pub fn online(&self, realm_id: u8) -> OnlineCounter {
+        self.realms.lock().unwrap().entry(realm_id).or_default().online.clone()
+    }
+
+    // the account is in the world of any realm
+    pub fn is_online(&self, account: &str) -> bool {
+        self.realms.lock().unwrap().values().any(|realm| realm.online.contains(account))
+    }
+
+    pub fn set_listening(&self, realm_id: u8, listening: bool) {
+        self.realms.lock().unwrap().entry(realm_id).or_default().listening = listening;
+    }
+
+    pub fn is_listening(&self, realm_id: u8) -> bool {
+        self.realms.lock().unwrap().get(&realm_id).map(|realm| realm.listening).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primary::server::RealmStatus;
+
+    #[test]
+    fn test_realm_status() {
+        let status = RealmStatus::new();
+        assert!(!status.is_listening(1));
+
+        status.set_listening(1, true);
+        let _guard = status.online(1).enter("TEST");
+
+        assert!(status.is_listening(1));
+        assert!(!status.is_listening(2));
+        assert_eq!(status.online(1).count(), 1);
+        assert_eq!(status.online(2).count(), 0);
+        assert!(status.is_online("test"));
+        assert!(!status.is_online("OTHER"));
+    }
+}
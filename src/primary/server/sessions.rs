@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex as SyncMutex};
+use std::time::{Duration, Instant};
+
+pub type SessionKey = Vec<u8>;
+
+#[derive(Debug)]
+struct SessionEntry {
+    key: SessionKey,
+    updated_at: Instant,
+}
+
+// session keys of authenticated accounts, login server writes them after LOGIN_PROOF
+// and world server reads them to verify CMSG_AUTH_SESSION
+#[derive(Debug)]
+pub struct Sessions {
+    ttl: Duration,
+    entries: SyncMutex<BTreeMap<String, SessionEntry>>,
+}
+
+impl Sessions {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: SyncMutex::new(BTreeMap::new()),
+        }
+    }
+
+    pub fn insert(&self, account: &str, key: SessionKey) {
+        self.entries.lock().unwrap().insert(account.to_uppercase(), SessionEntry {
+            key,
+            updated_at: Instant::now(),
+        });
+    }
+
+    pub fn get(&self, account: &str) -> Option<SessionKey> {
+        let mut entries = self.entries.lock().unwrap();
+        let account = account.to_uppercase();
+
+        match entries.get(&account) {
+            Some(entry) if entry.updated_at.elapsed() < self.ttl => Some(entry.key.clone()),
+            Some(_) => {
+                entries.remove(&account);
+                None
+            },
+            None => None,
+        }
+    }
+
+    // extends the session lifetime, for example after successful reconnect
+    pub fn touch(&self, account: &str) {
+        if let Some(entry) = self.entries.lock().unwrap().get_mut(&account.to_uppercase()) {
+            entry.updated_at = Instant::now();
+        }
+    }
+}
+
+// accounts which entered the world server
+#[derive(Debug, Default, Clone)]
+pub struct OnlineCounter(Arc<SyncMutex<Vec<String>>>);
+
+impl OnlineCounter {
+    pub fn count(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    pub fn contains(&self, account: &str) -> bool {
+        self.0.lock().unwrap().iter().any(|online| online.eq_ignore_ascii_case(account))
+    }
+
+    // the account stays online until returned guard is dropped together with connection session
+    pub fn enter(&self, account: &str) -> OnlineGuard {
+        self.0.lock().unwrap().push(account.to_uppercase());
+        OnlineGuard { accounts: Arc::clone(&self.0), account: account.to_uppercase() }
+    }
+}
+
+#[derive(Debug)]
+pub struct OnlineGuard {
+    accounts: Arc<SyncMutex<Vec<String>>>,
+    account: String,
+}
+
+impl Drop for OnlineGuard {
+    fn drop(&mut self) {
+        let mut accounts = self.accounts.lock().unwrap();
+        if let Some(index) = accounts.iter().position(|account| *account == self.account) {
+            accounts.remove(index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::primary::server::{OnlineCounter, Sessions};
+
+    #[test]
+    fn test_session_key_lookup() {
+        let sessions = Sessions::new(Duration::from_secs(60));
+        sessions.insert("test", vec![1, 2, 3]);
+
+        assert_eq!(sessions.get("TEST"), Some(vec![1, 2, 3]));
+        assert_eq!(sessions.get("OTHER"), None);
+    }
+
+    #[test]
+    fn test_expired_session_key() {
+        let sessions = Sessions::new(Duration::ZERO);
+        sessions.insert("test", vec![1, 2, 3]);
+
+        assert_eq!(sessions.get("TEST"), None);
+    }
+
+    #[test]
+    fn test_online_counter() {
+        let online = OnlineCounter::default();
+        let guard = online.enter("test");
+        let _other_guard = online.enter("OTHER");
+        assert_eq!(online.count(), 2);
+        assert!(online.contains("TEST"));
+
+        drop(guard);
+        assert_eq!(online.count(), 1);
+        assert!(!online.contains("TEST"));
+    }
+}
@@ -0,0 +1,64 @@
+use tokio::sync::watch;
+
+// broadcasts shutdown to servers and all of their connections
+#[derive(Debug)]
+pub struct Shutdown {
+    sender: watch::Sender<bool>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self {
+            sender: watch::Sender::new(false),
+        }
+    }
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn trigger(&self) {
+        self.sender.send_replace(true);
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        *self.sender.borrow()
+    }
+
+    // resolves immediately when the shutdown is already triggered
+    pub async fn wait(&self) {
+        let mut receiver = self.sender.subscribe();
+        // sender lives as long as self, so the error is not possible here
+        let _ = receiver.wait_for(|triggered| *triggered).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    use crate::primary::server::Shutdown;
+
+    #[tokio::test]
+    async fn test_shutdown_wakes_all_waiters() {
+        let shutdown = Arc::new(Shutdown::new());
+        let waiters: Vec<_> = (0..3).map(|_| {
+            let shutdown = Arc::clone(&shutdown);
+            tokio::spawn(async move { shutdown.wait().await })
+        }).collect();
+
+        assert!(!shutdown.is_triggered());
+        shutdown.trigger();
+
+        for waiter in waiters {
+            timeout(Duration::from_secs(1), waiter).await.unwrap().unwrap();
+        }
+
+        // late subscribers should not wait
+        timeout(Duration::from_secs(1), shutdown.wait()).await.unwrap();
+    }
+}
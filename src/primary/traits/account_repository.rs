@@ -0,0 +1,10 @@
+use std::fmt::Debug;
+use anyhow::{Result as AnyResult};
+
+use crate::primary::types::Account;
+
+pub trait AccountRepository: Debug + Send + Sync {
+    fn get(&self, name: &str) -> Option<Account>;
+
+    fn create(&self, name: &str, password: &str) -> AnyResult<Account>;
+}
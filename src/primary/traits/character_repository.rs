@@ -0,0 +1,35 @@
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+use crate::primary::types::Character;
+
+pub trait CharacterRepository: Debug + Send + Sync {
+    fn count(&self, account: &str, realm_id: u8) -> usize;
+    // characters of the account on the realm in the order they were created
+    fn list(&self, account: &str, realm_id: u8) -> Vec<Character>;
+    fn get(&self, guid: u64) -> Option<Character>;
+    // names are unique per realm regardless of the case
+    fn find_by_name(&self, name: &str, realm_id: u8) -> Option<Character>;
+    // stores the character with the new guid and returns it, the name is checked under the same lock
+    fn create(&self, character: Character) -> Result<Character, CharacterError>;
+    // replaces the stored character with the same guid, the character can keep its own name
+    fn update(&self, character: Character) -> Result<(), CharacterError>;
+    fn delete(&self, guid: u64) -> Option<Character>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CharacterError {
+    NameInUse,
+    NotFound,
+}
+
+impl Display for CharacterError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NameInUse => write!(f, "Character name is already in use"),
+            Self::NotFound => write!(f, "Character is not found"),
+        }
+    }
+}
+
+impl Error for CharacterError {}
@@ -1,4 +1,7 @@
+pub mod account_repository;
 pub mod binary_converter;
+pub mod character_repository;
+pub mod packet_codec;
 pub mod packet_handler;
 pub mod processor;
-pub mod server;
\ No newline at end of file
+pub mod server;
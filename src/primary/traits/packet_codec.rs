@@ -0,0 +1,20 @@
+use std::sync::{Arc, Mutex as SyncMutex};
+use anyhow::{Result as AnyResult};
+
+use crate::primary::types::{IncomingPacket, Session};
+
+// splits incoming byte stream into complete packets and prepares outgoing packets for the wire
+pub trait PacketCodec: Send {
+    // returns None if buffer does not contain complete packet yet,
+    // bytes of decoded packet are removed from the buffer
+    fn decode(
+        &mut self,
+        buffer: &mut Vec<u8>,
+        session: &Arc<SyncMutex<Session>>,
+    ) -> AnyResult<Option<IncomingPacket>>;
+
+    fn encode(&mut self, packet: Vec<u8>, session: &Arc<SyncMutex<Session>>) -> Vec<u8>;
+
+    // opcode and body of the packet passed to encode
+    fn outgoing_parts<'a>(&self, packet: &'a [u8]) -> (u16, &'a [u8]);
+}
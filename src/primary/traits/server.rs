@@ -1,134 +1,363 @@
 use std::sync::{Arc, Mutex as SyncMutex};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use anyhow::{Result as AnyResult};
 use async_trait::async_trait;
-use colored::Colorize;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::{TcpListener, TcpStream};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
 use tokio::sync::mpsc;
-use tokio::time::sleep;
-use crate::primary::crypto::srp::Srp;
-use crate::primary::types::{HandlerInput, HandlerOutput, ProcessorFunction, ProcessorResult};
+use tokio::task::JoinSet;
+use tokio::time::timeout;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tracing::{debug, error, field, info, info_span, warn, Instrument, Span};
+use crate::primary::capture::{CaptureRecord, CaptureWriter, Direction, ServerKind};
+use crate::primary::config::Config;
+use crate::primary::logging::{hex_dump, should_dump, PACKETS_TARGET};
+use crate::primary::server::{RealmStatus, Sessions, Shutdown};
+use crate::primary::traits::account_repository::AccountRepository;
+use crate::primary::traits::character_repository::CharacterRepository;
+#[cfg(test)]
+use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::traits::packet_codec::PacketCodec;
+use crate::primary::types::{HandlerInput, HandlerOutput, OutgoingPacket, ProcessorFunction, ProcessorResult, Session};
 
+#[derive(Debug)]
 pub struct RunOptions {
-    pub srp: Arc<SyncMutex<Srp>>,
+    pub accounts: Arc<dyn AccountRepository>,
+    pub characters: Arc<dyn CharacterRepository>,
+    // session keys of authenticated accounts
+    pub sessions: Arc<Sessions>,
+    // online accounts and listening state of every realm
+    pub realm_status: RealmStatus,
+    pub shutdown: Shutdown,
+    // decrypted packets of all connections are written here when capture is enabled
+    pub capture: Option<CaptureWriter>,
+    pub config: Config,
 }
 
-#[async_trait]
-pub trait Server: Send {
-    fn new() -> Self;
+#[cfg(test)]
+impl RunOptions {
+    // empty in-memory repositories and sessions, tests replace the parts they need
+    pub fn for_tests(config: Config) -> Self {
+        Self {
+            accounts: Arc::new(crate::primary::accounts::InMemoryAccountRepository::new()),
+            characters: Arc::new(crate::primary::characters::InMemoryCharacterRepository::new()),
+            sessions: Arc::new(Sessions::new(Duration::from_secs(60))),
+            realm_status: Default::default(),
+            shutdown: Default::default(),
+            capture: None,
+            config,
+        }
+    }
+}
+
+// input of one packet for handler tests, the session can be checked after the handler through input.session
+#[cfg(test)]
+pub fn test_input(options: &Arc<RunOptions>, session: Session, opcode: u32, data: Vec<u8>) -> HandlerInput {
+    HandlerInput {
+        data,
+        opcode: opcode as u16,
+        session: Arc::new(SyncMutex::new(session)),
+        options: Arc::clone(options),
+        json_details: None,
+    }
+}
+
+// runs the handler and returns the packets it sends, other outputs fail the test
+#[cfg(test)]
+pub async fn handle_packets<H: PacketHandler + Send + ?Sized>(handler: &mut H, input: &mut HandlerInput) -> Vec<OutgoingPacket> {
+    handler.handle(input).await.unwrap()
+        .into_iter()
+        .map(|output| match output {
+            HandlerOutput::Data(packet) => packet,
+            output => panic!("Unexpected output: {:?}", output),
+        })
+        .collect()
+}
 
-    async fn run(&mut self, options: Arc<RunOptions>) -> AnyResult<()> {
-        let (shutdown_tx, mut shutdown_rx) = mpsc::channel::<()>(1);
+// data of the first packet, login packets start with the opcode and world packets with the 6 bytes header
+#[cfg(test)]
+pub async fn handle_single<H: PacketHandler + Send + ?Sized>(handler: &mut H, input: &mut HandlerInput) -> Vec<u8> {
+    handle_packets(handler, input).await.remove(0).data
+}
+
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
 
-        let listener = TcpListener::bind(format!("{}:{}", Self::host(), Self::port())).await?;
-        println!("[{}] is started on port {}", Self::server_name(), Self::port().to_string());
+#[async_trait]
+pub trait Server: Send + Sync + 'static {
+    async fn run(self: Arc<Self>, options: Arc<RunOptions>) -> AnyResult<()> {
+        let mut connections = JoinSet::new();
+
+        let (host, port) = (self.host(&options), self.port(&options));
+        let listener = TcpListener::bind(format!("{}:{}", host, port)).await?;
+        info!("[{}] is started on {}:{}", self.server_name(), host, port);
+        self.started(&options);
 
         loop {
             tokio::select! {
                 stream = listener.accept() => {
                     match stream {
-                        Ok((socket, _)) => {
-                            let peer_addr = socket.peer_addr().unwrap();
-                            {
-                                let message = format!("Client connected: {:?}", peer_addr);
-                                println!("{}", message.yellow());
-                            }
+                        Ok((socket, peer_addr)) => {
+                            let connection_id = NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed);
+                            // account is recorded when the client is authenticated
+                            let span = info_span!(
+                                "connection",
+                                id = connection_id,
+                                server = %self.server_name(),
+                                peer = %peer_addr,
+                                account = field::Empty,
+                            );
 
-                            let shutdown_tx = shutdown_tx.clone();
                             let options = options.clone();
-                            tokio::spawn(async move {
-                                if let Err(err) = Self::handle_connection(socket, options).await {
-                                    eprintln!(
-                                        "[{}]: Error handling connection: {}",
-                                        Self::server_name(),
-                                        err
-                                    );
+                            let server = Arc::clone(&self);
+                            connections.spawn(async move {
+                                info!("Client connected");
+                                if let Err(err) = server.handle_connection(socket, connection_id, options).await {
+                                    error!("Error handling connection: {}", err);
                                 }
-
-                                drop(shutdown_tx);
-                            });
+                            }.instrument(span));
                         },
                         Err(err) => {
-                            eprintln!("Error accepting connection: {}", err);
+                            error!("[{}] error accepting connection: {}", self.server_name(), err);
                         }
                     }
                 },
-                _ = shutdown_rx.recv() => {
+                // finished connections are removed to not keep them till the shutdown
+                Some(_) = connections.join_next(), if !connections.is_empty() => {},
+                _ = options.shutdown.wait() => {
                     break;
                 }
             }
         }
 
+        drop(listener);
+        self.stopped(&options);
+
+        // connections are notified about the shutdown and can finish their work until drain timeout
+        let drain_timeout = Duration::from_secs(options.config.drain_timeout);
+        if timeout(drain_timeout, async { while connections.join_next().await.is_some() {} }).await.is_err() {
+            warn!("[{}] closing {} connection(s) after drain timeout", self.server_name(), connections.len());
+            connections.shutdown().await;
+        }
+        info!("[{}] is stopped", self.server_name());
+
         Ok(())
     }
 
-    async fn handle_connection(mut socket: TcpStream, options: Arc<RunOptions>) -> AnyResult<()> {
-        Self::init(&mut socket).await;
+    async fn handle_connection(
+        &self,
+        socket: TcpStream,
+        connection_id: u64,
+        options: Arc<RunOptions>,
+    ) -> AnyResult<()> {
+        let port = socket.local_addr()?.port();
+        let (reader, writer) = socket.into_split();
+        let (outbound, receiver) = mpsc::unbounded_channel::<OutgoingPacket>();
 
+        let session = Arc::new(SyncMutex::new(Session::new()));
+        {
+            let mut session = session.lock().unwrap();
+            session.outbound = Some(outbound);
+            session.connection_id = connection_id;
+            session.port = port;
+        }
+
+        // writer runs in the connection task, so it is dropped together with the connection
+        let write_packets = Self::write_packets(writer, receiver, Arc::clone(&session), Arc::clone(&options));
+        let read_packets = async {
+            self.init(&session).await;
+            let result = self.read_packets(reader, &session, &options).await;
+
+            // writer stops when all queued packets are sent and the sender is dropped
+            session.lock().unwrap().outbound = None;
+            result
+        };
+
+        let (result, write_result) = tokio::join!(read_packets, write_packets);
+        write_result?;
+
+        result
+    }
+
+    async fn read_packets(
+        &self,
+        mut reader: OwnedReadHalf,
+        session: &Arc<SyncMutex<Session>>,
+        options: &Arc<RunOptions>,
+    ) -> AnyResult<()> {
+        let mut codec = Self::codec();
+        let mut buffer = Vec::new();
+        let mut shutdown_notified = false;
+        // fields can be recorded into the span only once
+        let mut account_recorded = false;
+
+        let mut buf = [0; 65536];
         loop {
-            let mut buf = [0; 65536];
-            match socket.read(&mut buf).await {
+            let result = tokio::select! {
+                result = reader.read(&mut buf) => result,
+                _ = options.shutdown.wait(), if !shutdown_notified => {
+                    shutdown_notified = true;
+                    self.notify_shutdown(session, options);
+                    continue;
+                },
+            };
+
+            match result {
                 Ok(0) => {
-                    println!("{}", "Client disconnected".yellow());
+                    info!("Client disconnected");
                     break;
                 }
                 Ok(n) => {
-                    {
-                        let message = format!("Received {} bytes: {:?}", n, &buf[..n]);
-                        println!("{}", message.yellow());
-                    }
-                    let packet = &buf[..n];
-
-                    let mut input = Self::generate_input(packet, &options);
-
-                    let handler_list = Self::get_processors()
-                        .iter()
-                        .flat_map(|processor| processor(&mut input))
-                        .collect::<ProcessorResult>();
-
-                    for mut handler in handler_list {
-                        let response = handler.handle(&mut input).await;
-                        match response {
-                            Ok(outputs) => {
-                                for output in outputs {
-                                    match output {
-                                        HandlerOutput::Data(packet) => {
-                                            socket.write_all(&packet).await.unwrap();
-                                        },
-                                        HandlerOutput::SessionKey(_key) => {},
-                                    }
-                                }
-                            },
-                            Err(err) => {
-                                println!("[ERROR]: {}", err.to_string().red())
-                            },
-                        };
-                    }
+                    buffer.extend(&buf[..n]);
                 }
                 Err(e) => {
-                    eprintln!("Error reading from socket: {}", e);
+                    warn!("Error reading from socket: {}", e);
                     break;
                 }
             }
 
-            sleep(Duration::from_millis(100)).await;
+            while let Some(packet) = codec.decode(&mut buffer, session)? {
+                let opcode_name = Self::opcode_name(packet.opcode);
+                debug!("Received {} ({} bytes)", opcode_name, packet.body.len());
+                if should_dump(&options.config, &opcode_name) {
+                    info!(target: PACKETS_TARGET, "<- {}\n{}", opcode_name, hex_dump(&packet.body));
+                }
+
+                let mut input = HandlerInput {
+                    data: packet.body,
+                    opcode: packet.opcode,
+                    session: Arc::clone(session),
+                    options: Arc::clone(options),
+                    json_details: None,
+                };
+
+                let handler_list = Self::get_processors()
+                    .iter()
+                    .flat_map(|processor| processor(&mut input))
+                    .collect::<ProcessorResult>();
+
+                let mut responses = Vec::new();
+                for mut handler in handler_list {
+                    responses.push(handler.handle(&mut input).await);
+                }
+
+                // details are known only after the handler parsed the packet,
+                // but the packet should be captured before the responses
+                if let Some(json_details) = &input.json_details {
+                    debug!(target: PACKETS_TARGET, "<- {}\n{}", opcode_name, json_details);
+                }
+                Self::capture(options, session, Direction::Inbound, input.opcode, &input.data, input.json_details.clone());
+
+                for response in responses {
+                    match response {
+                        Ok(outputs) => {
+                            for output in outputs {
+                                match output {
+                                    HandlerOutput::Data(packet) => {
+                                        session.lock().unwrap().send(packet)?;
+                                    },
+                                    HandlerOutput::SessionKey(key) => {
+                                        let account = session.lock().unwrap().account.clone();
+                                        if let Some(account) = account {
+                                            options.sessions.insert(&account, key);
+                                        }
+                                    },
+                                }
+                            }
+                        },
+                        Err(err) => {
+                            error!("Error handling {}: {}", opcode_name, err);
+                        },
+                    };
+                }
+
+                if !account_recorded {
+                    if let Some(account) = &session.lock().unwrap().account {
+                        Span::current().record("account", account.as_str());
+                        account_recorded = true;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn write_packets(
+        mut writer: OwnedWriteHalf,
+        mut receiver: UnboundedReceiver<OutgoingPacket>,
+        session: Arc<SyncMutex<Session>>,
+        options: Arc<RunOptions>,
+    ) -> AnyResult<()> {
+        let mut codec = Self::codec();
+
+        while let Some(packet) = receiver.recv().await {
+            let OutgoingPacket { data, json_details, .. } = packet;
+            let (opcode, body) = codec.outgoing_parts(&data);
+            let opcode_name = Self::opcode_name(opcode);
+            debug!("Sending {} ({} bytes)", opcode_name, body.len());
+            debug!(target: PACKETS_TARGET, "-> {}\n{}", opcode_name, json_details);
+            if should_dump(&options.config, &opcode_name) {
+                info!(target: PACKETS_TARGET, "-> {}\n{}", opcode_name, hex_dump(body));
+            }
+            Self::capture(&options, &session, Direction::Outbound, opcode, body, Some(json_details));
+
+            let packet = codec.encode(data, &session);
+            writer.write_all(&packet).await?;
         }
 
         Ok(())
     }
 
-    async fn init(_socket: &mut TcpStream) {
+    fn capture(
+        options: &RunOptions,
+        session: &Arc<SyncMutex<Session>>,
+        direction: Direction,
+        opcode: u16,
+        body: &[u8],
+        json_details: Option<String>,
+    ) {
+        if let Some(capture) = &options.capture {
+            let (connection_id, port) = {
+                let session = session.lock().unwrap();
+                (session.connection_id, session.port)
+            };
+
+            let record = CaptureRecord::new(connection_id, port, Self::server_kind(), direction, opcode, body, json_details);
+            if let Err(err) = capture.write(&record) {
+                error!("Cannot write packet into capture: {}", err);
+            }
+        }
+    }
+
+    async fn init(&self, _session: &Arc<SyncMutex<Session>>) {
         // do nothing by default, but can contain some preparation steps
     }
 
-    fn generate_input(packet: &[u8], options: &RunOptions) -> HandlerInput;
+    fn notify_shutdown(&self, _session: &Arc<SyncMutex<Session>>, _options: &RunOptions) {
+        // called once for every connection when the shutdown is triggered
+    }
+
+    fn started(&self, _options: &RunOptions) {
+        // called when the server is listening
+    }
+
+    fn stopped(&self, _options: &RunOptions) {
+        // called when the server stopped accepting connections
+    }
+
+    fn codec() -> Box<dyn PacketCodec>;
 
     fn get_processors() -> Vec<ProcessorFunction>;
 
-    fn host<'a>() -> &'a str;
+    fn opcode_name(opcode: u16) -> String;
+
+    fn server_kind() -> ServerKind;
+
+    fn host(&self, options: &RunOptions) -> String;
 
-    fn port() -> u16;
+    fn port(&self, options: &RunOptions) -> u16;
 
-    fn server_name<'a>() -> &'a str;
+    fn server_name(&self) -> String;
 }
\ No newline at end of file
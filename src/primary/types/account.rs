@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+
+use crate::primary::crypto::srp::Srp;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Account {
+    pub name: String,
+    pub salt: [u8; 32],
+    pub verifier: Vec<u8>,
+    #[serde(default)]
+    pub banned: bool,
+    #[serde(default)]
+    pub suspended: bool,
+}
+
+impl Account {
+    pub fn new(name: &str, password: &str) -> Self {
+        let mut srp = Srp::new();
+        srp.set_account(name.to_string());
+        srp.set_salt(rand::random());
+        srp.generate_verifier::<Sha1>(password);
+
+        Self {
+            name: name.to_uppercase(),
+            salt: srp.salt,
+            verifier: srp.verifier().unwrap(),
+            banned: false,
+            suspended: false,
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Character {
+    // assigned by the repository on create
+    pub guid: u64,
+    // uppercased account name
+    pub account: String,
+    pub realm_id: u8,
+    pub name: String,
+    pub race: u8,
+    pub class: u8,
+    pub gender: u8,
+    pub skin: u8,
+    pub face: u8,
+    pub hair_style: u8,
+    pub hair_color: u8,
+    pub facial_hair: u8,
+    pub level: u8,
+    pub zone: u32,
+    pub map: u32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub orientation: f32,
+    // actions the client should complete on the character screen or after login, see AtLoginFlags
+    pub at_login: u16,
+}
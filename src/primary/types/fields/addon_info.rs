@@ -0,0 +1,35 @@
+use std::io::BufRead;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tentacli::errors::FieldError;
+use tentacli::traits::BinaryConverter;
+
+// compressed addon list at the end of CMSG_AUTH_SESSION, kept as is
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct AddonInfo(pub Vec<u8>);
+
+impl BinaryConverter for AddonInfo {
+    fn write_into(&mut self, buffer: &mut Vec<u8>) -> Result<(), FieldError> {
+        buffer.extend(&self.0);
+
+        Ok(())
+    }
+
+    fn read_from<R: BufRead>(mut reader: R) -> Result<Self, FieldError> {
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer).map_err(|e| FieldError::CannotRead(e, "addon_info:AddonInfo".to_string()))?;
+
+        Ok(Self(buffer))
+    }
+}
+
+impl<'de> Deserialize<'de> for AddonInfo {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        Ok(Self(Vec::deserialize(deserializer)?))
+    }
+}
+
+impl Serialize for AddonInfo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        self.0.serialize(serializer)
+    }
+}
@@ -0,0 +1,256 @@
+use std::io::{BufRead, Error, ErrorKind};
+use byteorder::{LittleEndian, ReadBytesExt};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tentacli::errors::FieldError;
+use tentacli::traits::BinaryConverter;
+
+// equipment slots shown on the character screen, bags included
+pub const EQUIPMENT_SLOTS: usize = 23;
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EquipmentItem {
+    pub display_id: u32,
+    pub inventory_type: u8,
+    pub enchant_aura_id: u32,
+}
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CharacterEntry {
+    pub guid: u64,
+    pub name: String,
+    pub race: u8,
+    pub class: u8,
+    pub gender: u8,
+    pub skin: u8,
+    pub face: u8,
+    pub hair_style: u8,
+    pub hair_color: u8,
+    pub facial_hair: u8,
+    pub level: u8,
+    pub zone: u32,
+    pub map: u32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub guild_id: u32,
+    pub flags: u32,
+    pub customize_flags: u32,
+    pub first_login: u8,
+    pub pet_display_id: u32,
+    pub pet_level: u32,
+    pub pet_family: u32,
+    pub equipment: [EquipmentItem; EQUIPMENT_SLOTS],
+}
+
+// characters of SMSG_CHAR_ENUM packet prefixed with u8 count
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct Characters(pub Vec<CharacterEntry>);
+
+impl BinaryConverter for Characters {
+    fn write_into(&mut self, buffer: &mut Vec<u8>) -> Result<(), FieldError> {
+        let count = u8::try_from(self.0.len()).map_err(|_| FieldError::CannotWrite(
+            Error::new(ErrorKind::InvalidData, "too many characters"),
+            "characters_count:u8 (Characters)".to_string(),
+        ))?;
+        buffer.push(count);
+
+        for character in self.0.iter() {
+            buffer.extend(character.guid.to_le_bytes());
+            buffer.extend(character.name.as_bytes());
+            buffer.push(0);
+            buffer.extend([
+                character.race,
+                character.class,
+                character.gender,
+                character.skin,
+                character.face,
+                character.hair_style,
+                character.hair_color,
+                character.facial_hair,
+                character.level,
+            ]);
+            buffer.extend(character.zone.to_le_bytes());
+            buffer.extend(character.map.to_le_bytes());
+            buffer.extend(character.x.to_le_bytes());
+            buffer.extend(character.y.to_le_bytes());
+            buffer.extend(character.z.to_le_bytes());
+            buffer.extend(character.guild_id.to_le_bytes());
+            buffer.extend(character.flags.to_le_bytes());
+            buffer.extend(character.customize_flags.to_le_bytes());
+            buffer.push(character.first_login);
+            buffer.extend(character.pet_display_id.to_le_bytes());
+            buffer.extend(character.pet_level.to_le_bytes());
+            buffer.extend(character.pet_family.to_le_bytes());
+
+            for item in character.equipment.iter() {
+                buffer.extend(item.display_id.to_le_bytes());
+                buffer.push(item.inventory_type);
+                buffer.extend(item.enchant_aura_id.to_le_bytes());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_from<R: BufRead>(mut reader: R) -> Result<Self, FieldError> {
+        let label = "Characters";
+        let read_error = |field: &str| {
+            let field = field.to_string();
+            move |e| FieldError::CannotRead(e, format!("{} ({})", field, label))
+        };
+
+        let count = reader.read_u8().map_err(read_error("characters_count:u8"))?;
+
+        let mut characters = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let guid = reader.read_u64::<LittleEndian>().map_err(read_error("guid:u64"))?;
+            let name = Self::read_string(&mut reader).map_err(read_error("name:String"))?;
+            let race = reader.read_u8().map_err(read_error("race:u8"))?;
+            let class = reader.read_u8().map_err(read_error("class:u8"))?;
+            let gender = reader.read_u8().map_err(read_error("gender:u8"))?;
+            let skin = reader.read_u8().map_err(read_error("skin:u8"))?;
+            let face = reader.read_u8().map_err(read_error("face:u8"))?;
+            let hair_style = reader.read_u8().map_err(read_error("hair_style:u8"))?;
+            let hair_color = reader.read_u8().map_err(read_error("hair_color:u8"))?;
+            let facial_hair = reader.read_u8().map_err(read_error("facial_hair:u8"))?;
+            let level = reader.read_u8().map_err(read_error("level:u8"))?;
+            let zone = reader.read_u32::<LittleEndian>().map_err(read_error("zone:u32"))?;
+            let map = reader.read_u32::<LittleEndian>().map_err(read_error("map:u32"))?;
+            let x = reader.read_f32::<LittleEndian>().map_err(read_error("x:f32"))?;
+            let y = reader.read_f32::<LittleEndian>().map_err(read_error("y:f32"))?;
+            let z = reader.read_f32::<LittleEndian>().map_err(read_error("z:f32"))?;
+            let guild_id = reader.read_u32::<LittleEndian>().map_err(read_error("guild_id:u32"))?;
+            let flags = reader.read_u32::<LittleEndian>().map_err(read_error("flags:u32"))?;
+            let customize_flags = reader.read_u32::<LittleEndian>().map_err(read_error("customize_flags:u32"))?;
+            let first_login = reader.read_u8().map_err(read_error("first_login:u8"))?;
+            let pet_display_id = reader.read_u32::<LittleEndian>().map_err(read_error("pet_display_id:u32"))?;
+            let pet_level = reader.read_u32::<LittleEndian>().map_err(read_error("pet_level:u32"))?;
+            let pet_family = reader.read_u32::<LittleEndian>().map_err(read_error("pet_family:u32"))?;
+
+            let mut equipment = [EquipmentItem::default(); EQUIPMENT_SLOTS];
+            for item in equipment.iter_mut() {
+                item.display_id = reader.read_u32::<LittleEndian>().map_err(read_error("display_id:u32"))?;
+                item.inventory_type = reader.read_u8().map_err(read_error("inventory_type:u8"))?;
+                item.enchant_aura_id = reader.read_u32::<LittleEndian>().map_err(read_error("enchant_aura_id:u32"))?;
+            }
+
+            characters.push(CharacterEntry {
+                guid,
+                name,
+                race,
+                class,
+                gender,
+                skin,
+                face,
+                hair_style,
+                hair_color,
+                facial_hair,
+                level,
+                zone,
+                map,
+                x,
+                y,
+                z,
+                guild_id,
+                flags,
+                customize_flags,
+                first_login,
+                pet_display_id,
+                pet_level,
+                pet_family,
+                equipment,
+            });
+        }
+
+        Ok(Self(characters))
+    }
+}
+
+impl Characters {
+    // reads null-terminated string, terminator is not included
+    fn read_string<R: BufRead>(reader: &mut R) -> Result<String, Error> {
+        let mut buffer = Vec::new();
+        reader.read_until(0, &mut buffer)?;
+        if buffer.pop() != Some(0) {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "string is not terminated"));
+        }
+
+        String::from_utf8(buffer).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+}
+
+impl<'de> Deserialize<'de> for Characters {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        Ok(Self(Vec::<CharacterEntry>::deserialize(deserializer)?))
+    }
+}
+
+impl Serialize for Characters {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tentacli::traits::BinaryConverter;
+
+    use crate::primary::types::fields::characters::{CharacterEntry, Characters, EquipmentItem};
+
+    fn characters() -> Characters {
+        let mut equipment = [EquipmentItem::default(); 23];
+        equipment[4] = EquipmentItem { display_id: 9891, inventory_type: 5, enchant_aura_id: 0 };
+
+        Characters(vec![
+            CharacterEntry {
+                guid: 1,
+                name: "First".to_string(),
+                race: 1,
+                class: 1,
+                level: 1,
+                zone: 12,
+                x: -8949.95,
+                y: -132.493,
+                z: 83.5312,
+                first_login: 1,
+                equipment,
+                ..CharacterEntry::default()
+            },
+            CharacterEntry {
+                guid: 2,
+                name: "Second".to_string(),
+                race: 2,
+                class: 6,
+                gender: 1,
+                level: 55,
+                map: 609,
+                ..CharacterEntry::default()
+            },
+        ])
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let mut buffer = Vec::new();
+        characters().write_into(&mut buffer).unwrap();
+
+        assert_eq!(buffer[0], 2);
+        assert_eq!(buffer[9..15], *b"First\0");
+        assert_eq!(Characters::read_from(buffer.as_slice()).unwrap(), characters());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let json = serde_json::to_string(&characters()).unwrap();
+
+        assert_eq!(serde_json::from_str::<Characters>(&json).unwrap(), characters());
+    }
+
+    #[test]
+    fn test_truncated_payload() {
+        let mut buffer = Vec::new();
+        characters().write_into(&mut buffer).unwrap();
+
+        assert!(Characters::read_from(&buffer[..buffer.len() - 1]).is_err());
+    }
+}
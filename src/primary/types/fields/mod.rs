@@ -1 +1,7 @@
-pub mod realms;
\ No newline at end of file
+pub mod addon_info;
+pub mod characters;
+pub mod movement;
+pub mod packed_guid;
+pub mod realms;
+pub mod spells;
+pub mod update_blocks;
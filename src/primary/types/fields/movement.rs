@@ -0,0 +1,362 @@
+use std::io::{BufRead, Error, ErrorKind};
+use byteorder::{LittleEndian, ReadBytesExt};
+use serde::{Deserialize, Serialize};
+use tentacli::errors::FieldError;
+use tentacli::traits::BinaryConverter;
+
+use crate::primary::types::fields::packed_guid::PackedGuid;
+
+#[non_exhaustive]
+pub struct MovementFlags;
+
+#[allow(dead_code)]
+impl MovementFlags {
+    pub const NONE: u32                 = 0x00000000;
+    pub const FORWARD: u32              = 0x00000001;
+    pub const BACKWARD: u32             = 0x00000002;
+    pub const STRAFE_LEFT: u32          = 0x00000004;
+    pub const STRAFE_RIGHT: u32         = 0x00000008;
+    pub const LEFT: u32                 = 0x00000010;
+    pub const RIGHT: u32                = 0x00000020;
+    pub const PITCH_UP: u32             = 0x00000040;
+    pub const PITCH_DOWN: u32           = 0x00000080;
+    pub const WALKING: u32              = 0x00000100;
+    pub const ON_TRANSPORT: u32         = 0x00000200;
+    pub const DISABLE_GRAVITY: u32      = 0x00000400;
+    pub const ROOT: u32                 = 0x00000800;
+    pub const FALLING: u32              = 0x00001000;
+    pub const FALLING_FAR: u32          = 0x00002000;
+    pub const PENDING_STOP: u32         = 0x00004000;
+    pub const PENDING_STRAFE_STOP: u32  = 0x00008000;
+    pub const PENDING_FORWARD: u32      = 0x00010000;
+    pub const PENDING_BACKWARD: u32     = 0x00020000;
+    pub const PENDING_STRAFE_LEFT: u32  = 0x00040000;
+    pub const PENDING_STRAFE_RIGHT: u32 = 0x00080000;
+    pub const PENDING_ROOT: u32         = 0x00100000;
+    pub const SWIMMING: u32             = 0x00200000;
+    pub const ASCENDING: u32            = 0x00400000;
+    pub const DESCENDING: u32           = 0x00800000;
+    pub const CAN_FLY: u32              = 0x01000000;
+    pub const FLYING: u32               = 0x02000000;
+    pub const SPLINE_ELEVATION: u32     = 0x04000000;
+    pub const SPLINE_ENABLED: u32       = 0x08000000;
+    pub const WATER_WALKING: u32        = 0x10000000;
+    pub const FALLING_SLOW: u32         = 0x20000000;
+    pub const HOVER: u32                = 0x40000000;
+}
+
+#[non_exhaustive]
+pub struct MovementExtraFlags;
+
+#[allow(dead_code)]
+impl MovementExtraFlags {
+    pub const NONE: u16                  = 0x0000;
+    pub const NO_STRAFE: u16             = 0x0001;
+    pub const NO_JUMPING: u16            = 0x0002;
+    pub const FULL_SPEED_TURNING: u16    = 0x0008;
+    pub const FULL_SPEED_PITCHING: u16   = 0x0010;
+    pub const ALWAYS_ALLOW_PITCHING: u16 = 0x0020;
+    pub const INTERPOLATED_MOVEMENT: u16 = 0x0400;
+    pub const INTERPOLATED_TURNING: u16  = 0x0800;
+    pub const INTERPOLATED_PITCHING: u16 = 0x1000;
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Position {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub orientation: f32,
+}
+
+impl BinaryConverter for Position {
+    fn write_into(&mut self, buffer: &mut Vec<u8>) -> Result<(), FieldError> {
+        for value in [self.x, self.y, self.z, self.orientation] {
+            buffer.extend(value.to_le_bytes());
+        }
+
+        Ok(())
+    }
+
+    fn read_from<R: BufRead>(mut reader: R) -> Result<Self, FieldError> {
+        Ok(Self {
+            x: reader.read_f32::<LittleEndian>().map_err(read_error("x:f32"))?,
+            y: reader.read_f32::<LittleEndian>().map_err(read_error("y:f32"))?,
+            z: reader.read_f32::<LittleEndian>().map_err(read_error("z:f32"))?,
+            orientation: reader.read_f32::<LittleEndian>().map_err(read_error("orientation:f32"))?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Speeds {
+    pub walk: f32,
+    pub run: f32,
+    pub run_back: f32,
+    pub swim: f32,
+    pub swim_back: f32,
+    pub flight: f32,
+    pub flight_back: f32,
+    pub turn: f32,
+    pub pitch: f32,
+}
+
+// base speeds of players and creatures
+impl Default for Speeds {
+    fn default() -> Self {
+        Self {
+            walk: 2.5,
+            run: 7.0,
+            run_back: 4.5,
+            swim: 4.722222,
+            swim_back: 2.5,
+            flight: 7.0,
+            flight_back: 4.5,
+            turn: 3.141594,
+            pitch: 3.141594,
+        }
+    }
+}
+
+impl BinaryConverter for Speeds {
+    fn write_into(&mut self, buffer: &mut Vec<u8>) -> Result<(), FieldError> {
+        for speed in [
+            self.walk, self.run, self.run_back, self.swim, self.swim_back,
+            self.flight, self.flight_back, self.turn, self.pitch,
+        ] {
+            buffer.extend(speed.to_le_bytes());
+        }
+
+        Ok(())
+    }
+
+    fn read_from<R: BufRead>(mut reader: R) -> Result<Self, FieldError> {
+        let mut speeds = [0f32; 9];
+        for speed in speeds.iter_mut() {
+            *speed = reader.read_f32::<LittleEndian>().map_err(read_error("speed:f32"))?;
+        }
+        let [walk, run, run_back, swim, swim_back, flight, flight_back, turn, pitch] = speeds;
+
+        Ok(Self { walk, run, run_back, swim, swim_back, flight, flight_back, turn, pitch })
+    }
+}
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TransportInfo {
+    pub guid: u64,
+    // offset from the transport position
+    pub position: Position,
+    pub time: u32,
+    pub seat: i8,
+    // written when extra flags contain INTERPOLATED_MOVEMENT
+    pub interpolated_time: Option<u32>,
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JumpInfo {
+    pub z_speed: f32,
+    pub sin_angle: f32,
+    pub cos_angle: f32,
+    pub xy_speed: f32,
+}
+
+// movement of the unit, every optional part is written when its flag is set
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MovementInfo {
+    pub flags: u32,
+    pub extra_flags: u16,
+    pub time: u32,
+    pub position: Position,
+    // ON_TRANSPORT
+    pub transport: Option<TransportInfo>,
+    // SWIMMING, FLYING or ALWAYS_ALLOW_PITCHING extra flag
+    pub pitch: Option<f32>,
+    pub fall_time: u32,
+    // FALLING
+    pub jump: Option<JumpInfo>,
+    // SPLINE_ELEVATION
+    pub spline_elevation: Option<f32>,
+}
+
+impl MovementInfo {
+    fn has_pitch(&self) -> bool {
+        self.flags & (MovementFlags::SWIMMING | MovementFlags::FLYING) != 0
+            || self.extra_flags & MovementExtraFlags::ALWAYS_ALLOW_PITCHING != 0
+    }
+}
+
+impl BinaryConverter for MovementInfo {
+    fn write_into(&mut self, buffer: &mut Vec<u8>) -> Result<(), FieldError> {
+        let missing = |field: &str| FieldError::CannotWrite(
+            Error::new(ErrorKind::InvalidData, "movement flag is set without its data"),
+            format!("{} (MovementInfo)", field),
+        );
+
+        buffer.extend(self.flags.to_le_bytes());
+        buffer.extend(self.extra_flags.to_le_bytes());
+        buffer.extend(self.time.to_le_bytes());
+        self.position.write_into(buffer)?;
+
+        if self.flags & MovementFlags::ON_TRANSPORT != 0 {
+            let mut transport = self.transport.clone().ok_or_else(|| missing("transport:TransportInfo"))?;
+            PackedGuid(transport.guid).write_into(buffer)?;
+            transport.position.write_into(buffer)?;
+            buffer.extend(transport.time.to_le_bytes());
+            buffer.extend(transport.seat.to_le_bytes());
+
+            if self.extra_flags & MovementExtraFlags::INTERPOLATED_MOVEMENT != 0 {
+                let time = transport.interpolated_time.ok_or_else(|| missing("interpolated_time:u32"))?;
+                buffer.extend(time.to_le_bytes());
+            }
+        }
+
+        if self.has_pitch() {
+            buffer.extend(self.pitch.ok_or_else(|| missing("pitch:f32"))?.to_le_bytes());
+        }
+
+        buffer.extend(self.fall_time.to_le_bytes());
+
+        if self.flags & MovementFlags::FALLING != 0 {
+            let jump = self.jump.ok_or_else(|| missing("jump:JumpInfo"))?;
+            for value in [jump.z_speed, jump.sin_angle, jump.cos_angle, jump.xy_speed] {
+                buffer.extend(value.to_le_bytes());
+            }
+        }
+
+        if self.flags & MovementFlags::SPLINE_ELEVATION != 0 {
+            buffer.extend(self.spline_elevation.ok_or_else(|| missing("spline_elevation:f32"))?.to_le_bytes());
+        }
+
+        Ok(())
+    }
+
+    fn read_from<R: BufRead>(mut reader: R) -> Result<Self, FieldError> {
+        let mut movement = Self {
+            flags: reader.read_u32::<LittleEndian>().map_err(read_error("flags:u32"))?,
+            extra_flags: reader.read_u16::<LittleEndian>().map_err(read_error("extra_flags:u16"))?,
+            time: reader.read_u32::<LittleEndian>().map_err(read_error("time:u32"))?,
+            position: Position::read_from(&mut reader)?,
+            ..Self::default()
+        };
+
+        if movement.flags & MovementFlags::ON_TRANSPORT != 0 {
+            let mut transport = TransportInfo {
+                guid: PackedGuid::read_from(&mut reader)?.0,
+                position: Position::read_from(&mut reader)?,
+                time: reader.read_u32::<LittleEndian>().map_err(read_error("transport_time:u32"))?,
+                seat: reader.read_i8().map_err(read_error("seat:i8"))?,
+                interpolated_time: None,
+            };
+
+            if movement.extra_flags & MovementExtraFlags::INTERPOLATED_MOVEMENT != 0 {
+                let time = reader.read_u32::<LittleEndian>().map_err(read_error("interpolated_time:u32"))?;
+                transport.interpolated_time = Some(time);
+            }
+
+            movement.transport = Some(transport);
+        }
+
+        if movement.has_pitch() {
+            movement.pitch = Some(reader.read_f32::<LittleEndian>().map_err(read_error("pitch:f32"))?);
+        }
+
+        movement.fall_time = reader.read_u32::<LittleEndian>().map_err(read_error("fall_time:u32"))?;
+
+        if movement.flags & MovementFlags::FALLING != 0 {
+            movement.jump = Some(JumpInfo {
+                z_speed: reader.read_f32::<LittleEndian>().map_err(read_error("z_speed:f32"))?,
+                sin_angle: reader.read_f32::<LittleEndian>().map_err(read_error("sin_angle:f32"))?,
+                cos_angle: reader.read_f32::<LittleEndian>().map_err(read_error("cos_angle:f32"))?,
+                xy_speed: reader.read_f32::<LittleEndian>().map_err(read_error("xy_speed:f32"))?,
+            });
+        }
+
+        if movement.flags & MovementFlags::SPLINE_ELEVATION != 0 {
+            let elevation = reader.read_f32::<LittleEndian>().map_err(read_error("spline_elevation:f32"))?;
+            movement.spline_elevation = Some(elevation);
+        }
+
+        Ok(movement)
+    }
+}
+
+fn read_error(field: &str) -> impl Fn(Error) -> FieldError {
+    let field = field.to_string();
+    move |e| FieldError::CannotRead(e, format!("{} (MovementInfo)", field))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use tentacli::packet::idewave::WorldPacket;
+    use tentacli::traits::BinaryConverter;
+
+    use crate::with_opcode;
+    use crate::primary::server::Opcode;
+    use crate::primary::types::fields::movement::{
+        JumpInfo, MovementExtraFlags, MovementFlags, MovementInfo, Position, Speeds, TransportInfo,
+    };
+    use crate::primary::types::fields::packed_guid::PackedGuid;
+
+    with_opcode! {
+        @world_opcode(Opcode::MSG_MOVE_HEARTBEAT)
+        #[derive(WorldPacket, Serialize, Deserialize, Debug)]
+        struct MoveHeartbeat {
+            guid: PackedGuid,
+            movement: MovementInfo,
+        }
+    }
+
+    fn movement() -> MovementInfo {
+        MovementInfo {
+            flags: MovementFlags::FORWARD | MovementFlags::ON_TRANSPORT | MovementFlags::SWIMMING
+                | MovementFlags::FALLING | MovementFlags::SPLINE_ELEVATION,
+            extra_flags: MovementExtraFlags::INTERPOLATED_MOVEMENT,
+            time: 1000,
+            position: Position { x: 1.0, y: 2.0, z: 3.0, orientation: 0.5 },
+            transport: Some(TransportInfo {
+                guid: 0x1FC0000000000005,
+                position: Position { x: 0.1, y: 0.2, z: 0.3, orientation: 0.0 },
+                time: 200,
+                seat: -1,
+                interpolated_time: Some(300),
+            }),
+            pitch: Some(0.25),
+            fall_time: 400,
+            jump: Some(JumpInfo { z_speed: -7.9, sin_angle: 0.0, cos_angle: 1.0, xy_speed: 7.0 }),
+            spline_elevation: Some(1.5),
+        }
+    }
+
+    #[test]
+    fn test_movement_in_packet() {
+        let packet = MoveHeartbeat { guid: PackedGuid(2), movement: movement() }.unpack().unwrap();
+        let body = &packet.data[6..];
+
+        // packed guid, then flags and extra flags
+        assert_eq!(body[..3], [0x01, 0x02, 0x01]);
+        assert_eq!(body.len(), 2 + 4 + 2 + 4 + 16 + (4 + 16 + 4 + 1 + 4) + 4 + 4 + 16 + 4);
+
+        let (received, json_details) = MoveHeartbeat::from_binary(body).unwrap();
+        assert_eq!(received.guid, PackedGuid(2));
+        assert_eq!(received.movement, movement());
+        let json = serde_json::from_str::<serde_json::Value>(&json_details).unwrap();
+        assert_eq!(serde_json::from_value::<MovementInfo>(json["movement"].clone()).unwrap(), movement());
+    }
+
+    #[test]
+    fn test_missing_flag_data() {
+        let mut movement = MovementInfo { flags: MovementFlags::FALLING, ..MovementInfo::default() };
+
+        assert!(movement.write_into(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_speeds() {
+        let mut buffer = Vec::new();
+        Speeds::default().write_into(&mut buffer).unwrap();
+
+        assert_eq!(buffer.len(), 36);
+        assert_eq!(Speeds::read_from(buffer.as_slice()).unwrap(), Speeds::default());
+    }
+}
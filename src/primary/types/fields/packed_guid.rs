@@ -0,0 +1,77 @@
+use std::io::BufRead;
+use byteorder::ReadBytesExt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tentacli::errors::FieldError;
+use tentacli::traits::BinaryConverter;
+
+// guid prefixed with the mask of its non-zero bytes, only these bytes are written
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct PackedGuid(pub u64);
+
+impl BinaryConverter for PackedGuid {
+    fn write_into(&mut self, buffer: &mut Vec<u8>) -> Result<(), FieldError> {
+        let mask_index = buffer.len();
+        buffer.push(0);
+
+        for (index, byte) in self.0.to_le_bytes().into_iter().enumerate() {
+            if byte != 0 {
+                buffer[mask_index] |= 1 << index;
+                buffer.push(byte);
+            }
+        }
+
+        Ok(())
+    }
+
+    // the mask can also cover zero bytes, the client accepts such guids
+    fn read_from<R: BufRead>(mut reader: R) -> Result<Self, FieldError> {
+        let read_error = |e| FieldError::CannotRead(e, "guid:PackedGuid".to_string());
+
+        let mask = reader.read_u8().map_err(read_error)?;
+
+        let mut bytes = [0u8; 8];
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            if mask & (1 << index) != 0 {
+                *byte = reader.read_u8().map_err(read_error)?;
+            }
+        }
+
+        Ok(Self(u64::from_le_bytes(bytes)))
+    }
+}
+
+impl<'de> Deserialize<'de> for PackedGuid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        Ok(Self(u64::deserialize(deserializer)?))
+    }
+}
+
+impl Serialize for PackedGuid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tentacli::traits::BinaryConverter;
+
+    use crate::primary::types::fields::packed_guid::PackedGuid;
+
+    #[test]
+    fn test_packed_guid() {
+        let mut buffer = Vec::new();
+        PackedGuid(0xF130000000001234).write_into(&mut buffer).unwrap();
+        PackedGuid(0).write_into(&mut buffer).unwrap();
+
+        assert_eq!(buffer, [0b1100_0011, 0x34, 0x12, 0x30, 0xF1, 0]);
+
+        let mut reader = buffer.as_slice();
+        assert_eq!(PackedGuid::read_from(&mut reader).unwrap(), PackedGuid(0xF130000000001234));
+        assert_eq!(PackedGuid::read_from(&mut reader).unwrap(), PackedGuid(0));
+        // zero byte under the mask is not minimal but valid
+        assert_eq!(PackedGuid::read_from([0x03, 0x01, 0x00].as_slice()).unwrap(), PackedGuid(1));
+        // mask promises more bytes than there are
+        assert!(PackedGuid::read_from([0x03, 0x01].as_slice()).is_err());
+    }
+}
@@ -1,16 +1,42 @@
-use std::io::BufRead;
+use std::io::{BufRead, Error, ErrorKind};
+use byteorder::{LittleEndian, ReadBytesExt};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tentacli::errors::FieldError;
 use tentacli::traits::BinaryConverter;
 
-use std::fmt::{Debug};
-use tentacli::realm::Realm;
+use crate::primary::server::RealmFlags;
 
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RealmBuild {
+    pub major: u8,
+    pub minor: u8,
+    pub bugfix: u8,
+    pub build: u16,
+}
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Realm {
+    pub icon: u8,
+    pub lock: u8,
+    pub flags: u8,
+    pub name: String,
+    pub address: String,
+    pub population: f32,
+    pub characters: u8,
+    pub timezone: u8,
+    pub server_id: u8,
+    // written only when flags contain RealmFlags::SPECIFY_BUILD
+    pub build: Option<RealmBuild>,
+}
+
+// realms of REALM_LIST packet prefixed with u16 count
+#[derive(Clone, Default, Debug, PartialEq)]
 pub struct Realms(pub Vec<Realm>);
 
 impl BinaryConverter for Realms {
     fn write_into(&mut self, buffer: &mut Vec<u8>) -> Result<(), FieldError> {
+        buffer.extend((self.0.len() as u16).to_le_bytes());
+
         for realm in self.0.iter_mut() {
             buffer.push(realm.icon);
             buffer.push(realm.lock);
@@ -23,19 +49,86 @@ impl BinaryConverter for Realms {
             buffer.push(realm.characters);
             buffer.push(realm.timezone);
             buffer.push(realm.server_id);
+
+            if realm.flags & RealmFlags::SPECIFY_BUILD != 0 {
+                let build = realm.build.clone().ok_or_else(|| FieldError::CannotWrite(
+                    Error::new(ErrorKind::InvalidData, "build info is missing"),
+                    format!("build:RealmBuild (Realms: {})", realm.name),
+                ))?;
+
+                buffer.extend([build.major, build.minor, build.bugfix]);
+                buffer.extend(build.build.to_le_bytes());
+            }
         }
 
         Ok(())
     }
 
-    fn read_from<R: BufRead>(_reader: R) -> Result<Self, FieldError> {
-        todo!()
+    fn read_from<R: BufRead>(mut reader: R) -> Result<Self, FieldError> {
+        let label = "Realms";
+        let read_error = |field: &str| {
+            let field = field.to_string();
+            move |e| FieldError::CannotRead(e, format!("{} ({})", field, label))
+        };
+
+        let count = reader.read_u16::<LittleEndian>().map_err(read_error("realms_count:u16"))?;
+
+        let mut realms = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let icon = reader.read_u8().map_err(read_error("icon:u8"))?;
+            let lock = reader.read_u8().map_err(read_error("lock:u8"))?;
+            let flags = reader.read_u8().map_err(read_error("flags:u8"))?;
+            let name = Self::read_string(&mut reader).map_err(read_error("name:String"))?;
+            let address = Self::read_string(&mut reader).map_err(read_error("address:String"))?;
+            let population = reader.read_f32::<LittleEndian>().map_err(read_error("population:f32"))?;
+            let characters = reader.read_u8().map_err(read_error("characters:u8"))?;
+            let timezone = reader.read_u8().map_err(read_error("timezone:u8"))?;
+            let server_id = reader.read_u8().map_err(read_error("server_id:u8"))?;
+
+            let build = match flags & RealmFlags::SPECIFY_BUILD {
+                0 => None,
+                _ => Some(RealmBuild {
+                    major: reader.read_u8().map_err(read_error("major:u8"))?,
+                    minor: reader.read_u8().map_err(read_error("minor:u8"))?,
+                    bugfix: reader.read_u8().map_err(read_error("bugfix:u8"))?,
+                    build: reader.read_u16::<LittleEndian>().map_err(read_error("build:u16"))?,
+                }),
+            };
+
+            realms.push(Realm {
+                icon,
+                lock,
+                flags,
+                name,
+                address,
+                population,
+                characters,
+                timezone,
+                server_id,
+                build,
+            });
+        }
+
+        Ok(Self(realms))
+    }
+}
+
+impl Realms {
+    // reads null-terminated string, terminator is not included
+    fn read_string<R: BufRead>(reader: &mut R) -> Result<String, Error> {
+        let mut buffer = Vec::new();
+        reader.read_until(0, &mut buffer)?;
+        if buffer.pop() != Some(0) {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "string is not terminated"));
+        }
+
+        String::from_utf8(buffer).map_err(|e| Error::new(ErrorKind::InvalidData, e))
     }
 }
 
 impl<'de> Deserialize<'de> for Realms {
-    fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
-        todo!()
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        Ok(Self(Vec::<Realm>::deserialize(deserializer)?))
     }
 }
 
@@ -43,4 +136,69 @@ impl Serialize for Realms {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
         self.0.serialize(serializer)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use tentacli::traits::BinaryConverter;
+
+    use crate::primary::server::RealmFlags;
+    use crate::primary::types::fields::realms::{Realm, RealmBuild, Realms};
+
+    fn realms() -> Realms {
+        Realms(vec![
+            Realm {
+                icon: 1,
+                flags: RealmFlags::RECOMMENDED,
+                name: "First".to_string(),
+                address: "127.0.0.1:8999".to_string(),
+                population: 1.5,
+                characters: 2,
+                timezone: 1,
+                server_id: 1,
+                ..Realm::default()
+            },
+            Realm {
+                lock: 1,
+                flags: RealmFlags::SPECIFY_BUILD,
+                name: "Second".to_string(),
+                address: "127.0.0.1:9000".to_string(),
+                server_id: 2,
+                build: Some(RealmBuild { major: 3, minor: 3, bugfix: 5, build: 12340 }),
+                ..Realm::default()
+            },
+        ])
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let mut buffer = Vec::new();
+        realms().write_into(&mut buffer).unwrap();
+
+        assert_eq!(buffer[..2], [2, 0]);
+        assert_eq!(buffer[buffer.len() - 5..], [3, 3, 5, 0x34, 0x30]);
+        assert_eq!(Realms::read_from(buffer.as_slice()).unwrap(), realms());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let json = serde_json::to_string(&realms()).unwrap();
+
+        assert_eq!(serde_json::from_str::<Realms>(&json).unwrap(), realms());
+    }
+
+    #[test]
+    fn test_truncated_payload() {
+        let mut buffer = Vec::new();
+        realms().write_into(&mut buffer).unwrap();
+
+        assert!(Realms::read_from(&buffer[..buffer.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_missing_build_info() {
+        let mut realms = Realms(vec![Realm { flags: RealmFlags::SPECIFY_BUILD, ..Realm::default() }]);
+
+        assert!(realms.write_into(&mut Vec::new()).is_err());
+    }
+}
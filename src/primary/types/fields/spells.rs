@@ -0,0 +1,154 @@
+use std::io::{BufRead, Error, ErrorKind};
+use byteorder::{LittleEndian, ReadBytesExt};
+use serde::{Deserialize, Serialize};
+use tentacli::errors::FieldError;
+use tentacli::traits::BinaryConverter;
+
+// action bar slots of SMSG_ACTION_BUTTONS, the client expects all of them
+pub const ACTION_BUTTONS: usize = 144;
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpellCooldown {
+    pub spell_id: u32,
+    pub item_id: u16,
+    pub category: u16,
+    pub cooldown: u32,
+    pub category_cooldown: u32,
+}
+
+// known spells and cooldowns of SMSG_INITIAL_SPELLS, both prefixed with u16 count
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct InitialSpells {
+    pub spells: Vec<u32>,
+    pub cooldowns: Vec<SpellCooldown>,
+}
+
+impl BinaryConverter for InitialSpells {
+    fn write_into(&mut self, buffer: &mut Vec<u8>) -> Result<(), FieldError> {
+        buffer.extend((self.spells.len() as u16).to_le_bytes());
+        for spell_id in self.spells.iter() {
+            buffer.extend(spell_id.to_le_bytes());
+            // unused slot
+            buffer.extend(0u16.to_le_bytes());
+        }
+
+        buffer.extend((self.cooldowns.len() as u16).to_le_bytes());
+        for cooldown in self.cooldowns.iter() {
+            buffer.extend(cooldown.spell_id.to_le_bytes());
+            buffer.extend(cooldown.item_id.to_le_bytes());
+            buffer.extend(cooldown.category.to_le_bytes());
+            buffer.extend(cooldown.cooldown.to_le_bytes());
+            buffer.extend(cooldown.category_cooldown.to_le_bytes());
+        }
+
+        Ok(())
+    }
+
+    fn read_from<R: BufRead>(mut reader: R) -> Result<Self, FieldError> {
+        let label = "InitialSpells";
+        let read_error = |field: &str| {
+            let field = field.to_string();
+            move |e| FieldError::CannotRead(e, format!("{} ({})", field, label))
+        };
+
+        let count = reader.read_u16::<LittleEndian>().map_err(read_error("spells_count:u16"))?;
+        let mut spells = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            spells.push(reader.read_u32::<LittleEndian>().map_err(read_error("spell_id:u32"))?);
+            reader.read_u16::<LittleEndian>().map_err(read_error("slot:u16"))?;
+        }
+
+        let count = reader.read_u16::<LittleEndian>().map_err(read_error("cooldowns_count:u16"))?;
+        let mut cooldowns = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            cooldowns.push(SpellCooldown {
+                spell_id: reader.read_u32::<LittleEndian>().map_err(read_error("spell_id:u32"))?,
+                item_id: reader.read_u16::<LittleEndian>().map_err(read_error("item_id:u16"))?,
+                category: reader.read_u16::<LittleEndian>().map_err(read_error("category:u16"))?,
+                cooldown: reader.read_u32::<LittleEndian>().map_err(read_error("cooldown:u32"))?,
+                category_cooldown: reader.read_u32::<LittleEndian>().map_err(read_error("category_cooldown:u32"))?,
+            });
+        }
+
+        Ok(Self { spells, cooldowns })
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActionButton {
+    // spell, item or macro id depending on the type
+    pub action: u32,
+    pub action_type: u8,
+}
+
+// all slots of SMSG_ACTION_BUTTONS, missing ones are written empty
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ActionButtons(pub Vec<ActionButton>);
+
+impl BinaryConverter for ActionButtons {
+    fn write_into(&mut self, buffer: &mut Vec<u8>) -> Result<(), FieldError> {
+        if self.0.len() > ACTION_BUTTONS {
+            return Err(FieldError::CannotWrite(
+                Error::new(ErrorKind::InvalidData, "too many action buttons"),
+                "buttons:u32 (ActionButtons)".to_string(),
+            ));
+        }
+
+        for index in 0..ACTION_BUTTONS {
+            let button = self.0.get(index).copied().unwrap_or_default();
+            // action takes 3 lower bytes, type is the highest one
+            buffer.extend(((button.action & 0x00FFFFFF) | ((button.action_type as u32) << 24)).to_le_bytes());
+        }
+
+        Ok(())
+    }
+
+    fn read_from<R: BufRead>(mut reader: R) -> Result<Self, FieldError> {
+        let mut buttons = Vec::with_capacity(ACTION_BUTTONS);
+        for _ in 0..ACTION_BUTTONS {
+            let packed = reader.read_u32::<LittleEndian>()
+                .map_err(|e| FieldError::CannotRead(e, "button:u32 (ActionButtons)".to_string()))?;
+
+            buttons.push(ActionButton {
+                action: packed & 0x00FFFFFF,
+                action_type: (packed >> 24) as u8,
+            });
+        }
+
+        Ok(Self(buttons))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tentacli::traits::BinaryConverter;
+
+    use crate::primary::types::fields::spells::{ActionButton, ActionButtons, InitialSpells, SpellCooldown};
+
+    #[test]
+    fn test_initial_spells_round_trip() {
+        let spells = InitialSpells {
+            spells: vec![6603, 668],
+            cooldowns: vec![SpellCooldown { spell_id: 8690, cooldown: 1800000, ..SpellCooldown::default() }],
+        };
+
+        let mut buffer = Vec::new();
+        spells.clone().write_into(&mut buffer).unwrap();
+
+        assert_eq!(buffer.len(), 2 + 2 * 6 + 2 + 16);
+        assert_eq!(InitialSpells::read_from(buffer.as_slice()).unwrap(), spells);
+    }
+
+    #[test]
+    fn test_action_buttons_are_padded() {
+        let mut buffer = Vec::new();
+        ActionButtons(vec![ActionButton { action: 6603, action_type: 0x40 }]).write_into(&mut buffer).unwrap();
+
+        assert_eq!(buffer.len(), 144 * 4);
+        assert_eq!(buffer[..4], (6603u32 | 0x40000000).to_le_bytes());
+
+        let ActionButtons(buttons) = ActionButtons::read_from(buffer.as_slice()).unwrap();
+        assert_eq!(buttons[0], ActionButton { action: 6603, action_type: 0x40 });
+        assert_eq!(buttons[1], ActionButton::default());
+    }
+}
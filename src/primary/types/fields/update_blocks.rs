@@ -0,0 +1,462 @@
+use std::collections::BTreeMap;
+use std::io::{BufRead, Error, ErrorKind};
+use byteorder::{LittleEndian, ReadBytesExt};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use tentacli::errors::FieldError;
+use tentacli::traits::BinaryConverter;
+
+use crate::primary::objects::types::{UpdateFlags, UpdateType};
+use crate::primary::types::fields::movement::{MovementFlags, MovementInfo, Position, Speeds};
+use crate::primary::types::fields::packed_guid::PackedGuid;
+
+// bit per update field, the client reads values only for the set bits
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct UpdateMask {
+    blocks: Vec<u32>,
+}
+
+impl UpdateMask {
+    pub fn new(fields_count: u16) -> Self {
+        Self { blocks: vec![0; (fields_count as usize).div_ceil(32)] }
+    }
+
+    pub fn set(&mut self, index: u16) {
+        self.blocks[index as usize / 32] |= 1 << (index % 32);
+    }
+
+    pub fn is_set(&self, index: u16) -> bool {
+        self.blocks.get(index as usize / 32).is_some_and(|block| block & (1 << (index % 32)) != 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.blocks.iter_mut().for_each(|block| *block = 0);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.blocks.iter().all(|block| *block == 0)
+    }
+
+    pub fn blocks(&self) -> &[u32] {
+        &self.blocks
+    }
+
+    pub fn indexes(&self) -> impl Iterator<Item = u16> + '_ {
+        (0..self.blocks.len() * 32).map(|index| index as u16).filter(|index| self.is_set(*index))
+    }
+}
+
+// values of the update fields by index, the mask covers all fields of the object type
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UpdateValues {
+    pub mask_blocks: u8,
+    pub fields: BTreeMap<u16, u32>,
+}
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LivingMovement {
+    pub movement: MovementInfo,
+    pub speeds: Speeds,
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Vehicle {
+    pub id: u32,
+    pub orientation: f32,
+}
+
+// part of the create block, every optional part is written when its update flag is set
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MovementBlock {
+    pub update_flags: u16,
+    pub living: Option<LivingMovement>,
+    // stationary position of objects which are not living
+    pub position: Option<Position>,
+    pub low_guid: Option<u32>,
+    pub target: Option<u64>,
+    pub transport_time: Option<u32>,
+    pub vehicle: Option<Vehicle>,
+    pub rotation: Option<u64>,
+}
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateBlock {
+    pub guid: u64,
+    pub object_type: u8,
+    pub movement: MovementBlock,
+    pub values: UpdateValues,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum UpdateBlock {
+    Values { guid: u64, values: UpdateValues },
+    CreateObject(CreateBlock),
+    // CREATE_OBJECT2 for objects which appear in the world for the first time
+    SpawnObject(CreateBlock),
+    OutOfRange { guids: Vec<u64> },
+    NearObjects { guids: Vec<u64> },
+}
+
+// blocks of SMSG_UPDATE_OBJECT packet prefixed with u32 count
+#[derive(Clone, Default, Debug, PartialEq)]
+pub struct UpdateBlocks(pub Vec<UpdateBlock>);
+
+impl BinaryConverter for UpdateBlocks {
+    fn write_into(&mut self, buffer: &mut Vec<u8>) -> Result<(), FieldError> {
+        buffer.extend((self.0.len() as u32).to_le_bytes());
+
+        for block in self.0.iter() {
+            match block {
+                UpdateBlock::Values { guid, values } => {
+                    buffer.push(UpdateType::VALUES);
+                    PackedGuid(*guid).write_into(buffer)?;
+                    Self::write_values(buffer, values)?;
+                },
+                UpdateBlock::CreateObject(create) | UpdateBlock::SpawnObject(create) => {
+                    buffer.push(match block {
+                        UpdateBlock::CreateObject(_) => UpdateType::CREATE_OBJECT,
+                        _ => UpdateType::CREATE_OBJECT2,
+                    });
+                    PackedGuid(create.guid).write_into(buffer)?;
+                    buffer.push(create.object_type);
+                    Self::write_movement(buffer, &create.movement)?;
+                    Self::write_values(buffer, &create.values)?;
+                },
+                UpdateBlock::OutOfRange { guids } | UpdateBlock::NearObjects { guids } => {
+                    buffer.push(match block {
+                        UpdateBlock::OutOfRange { .. } => UpdateType::OUT_OF_RANGE_OBJECTS,
+                        _ => UpdateType::NEAR_OBJECTS,
+                    });
+                    buffer.extend((guids.len() as u32).to_le_bytes());
+                    for guid in guids {
+                        PackedGuid(*guid).write_into(buffer)?;
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    fn read_from<R: BufRead>(mut reader: R) -> Result<Self, FieldError> {
+        let count = reader.read_u32::<LittleEndian>().map_err(Self::read_error("blocks_count:u32"))?;
+
+        let mut blocks = Vec::with_capacity(count as usize);
+        for _ in 0..count {
+            let update_type = reader.read_u8().map_err(Self::read_error("update_type:u8"))?;
+
+            let block = match update_type {
+                UpdateType::VALUES => UpdateBlock::Values {
+                    guid: PackedGuid::read_from(&mut reader)?.0,
+                    values: Self::read_values(&mut reader)?,
+                },
+                UpdateType::CREATE_OBJECT | UpdateType::CREATE_OBJECT2 => {
+                    let create = CreateBlock {
+                        guid: PackedGuid::read_from(&mut reader)?.0,
+                        object_type: reader.read_u8().map_err(Self::read_error("object_type:u8"))?,
+                        movement: Self::read_movement(&mut reader)?,
+                        values: Self::read_values(&mut reader)?,
+                    };
+
+                    match update_type {
+                        UpdateType::CREATE_OBJECT => UpdateBlock::CreateObject(create),
+                        _ => UpdateBlock::SpawnObject(create),
+                    }
+                },
+                UpdateType::OUT_OF_RANGE_OBJECTS | UpdateType::NEAR_OBJECTS => {
+                    let count = reader.read_u32::<LittleEndian>().map_err(Self::read_error("guids_count:u32"))?;
+                    let guids = (0..count)
+                        .map(|_| PackedGuid::read_from(&mut reader).map(|guid| guid.0))
+                        .collect::<Result<Vec<u64>, FieldError>>()?;
+
+                    match update_type {
+                        UpdateType::OUT_OF_RANGE_OBJECTS => UpdateBlock::OutOfRange { guids },
+                        _ => UpdateBlock::NearObjects { guids },
+                    }
+                },
+                _ => return Err(Self::invalid_data("update_type:u8", "unsupported update type")),
+            };
+
+            blocks.push(block);
+        }
+
+        Ok(Self(blocks))
+    }
+}
+
+impl UpdateBlocks {
+    fn read_error(field: &str) -> impl Fn(Error) -> FieldError {
+        let field = field.to_string();
+        move |e| FieldError::CannotRead(e, format!("{} (UpdateBlocks)", field))
+    }
+
+    fn invalid_data(field: &str, message: &str) -> FieldError {
+        Self::read_error(field)(Error::new(ErrorKind::InvalidData, message.to_string()))
+    }
+
+    fn write_values(buffer: &mut Vec<u8>, values: &UpdateValues) -> Result<(), FieldError> {
+        let fields_count = values.mask_blocks as u16 * 32;
+        if values.fields.keys().any(|index| *index >= fields_count) {
+            return Err(FieldError::CannotWrite(
+                Error::new(ErrorKind::InvalidData, "field index is out of the mask"),
+                "fields:UpdateValues (UpdateBlocks)".to_string(),
+            ));
+        }
+
+        let mut mask = UpdateMask::new(fields_count);
+        values.fields.keys().for_each(|index| mask.set(*index));
+
+        buffer.push(values.mask_blocks);
+        for value in mask.blocks().iter().chain(values.fields.values()) {
+            buffer.extend(value.to_le_bytes());
+        }
+
+        Ok(())
+    }
+
+    fn read_values<R: BufRead>(reader: &mut R) -> Result<UpdateValues, FieldError> {
+        let mask_blocks = reader.read_u8().map_err(Self::read_error("mask_blocks:u8"))?;
+
+        let mut mask = UpdateMask::new(mask_blocks as u16 * 32);
+        for block in 0..mask_blocks as u16 {
+            let bits = reader.read_u32::<LittleEndian>().map_err(Self::read_error("mask:u32"))?;
+            (0..32).filter(|bit| bits & (1 << bit) != 0).for_each(|bit| mask.set(block * 32 + bit));
+        }
+
+        let mut fields = BTreeMap::new();
+        for index in mask.indexes() {
+            fields.insert(index, reader.read_u32::<LittleEndian>().map_err(Self::read_error("value:u32"))?);
+        }
+
+        Ok(UpdateValues { mask_blocks, fields })
+    }
+
+    fn write_movement(buffer: &mut Vec<u8>, movement: &MovementBlock) -> Result<(), FieldError> {
+        let flags = movement.update_flags;
+        let missing = |field: &str| FieldError::CannotWrite(
+            Error::new(ErrorKind::InvalidData, "update flag is set without its data"),
+            format!("{} (UpdateBlocks)", field),
+        );
+
+        buffer.extend(flags.to_le_bytes());
+
+        if flags & UpdateFlags::LIVING != 0 {
+            let mut living = movement.living.clone().ok_or_else(|| missing("living:LivingMovement"))?;
+            if living.movement.flags & MovementFlags::SPLINE_ENABLED != 0 {
+                return Err(FieldError::CannotWrite(
+                    Error::new(ErrorKind::InvalidData, "spline movement is not supported"),
+                    "movement_flags:u32 (UpdateBlocks)".to_string(),
+                ));
+            }
+            living.movement.write_into(buffer)?;
+            living.speeds.write_into(buffer)?;
+        } else if flags & UpdateFlags::POSITION != 0 {
+            return Err(FieldError::CannotWrite(
+                Error::new(ErrorKind::InvalidData, "transport position is not supported"),
+                "update_flags:u16 (UpdateBlocks)".to_string(),
+            ));
+        } else if flags & UpdateFlags::STATIONARY_POSITION != 0 {
+            movement.position.ok_or_else(|| missing("position:Position"))?.write_into(buffer)?;
+        }
+
+        if flags & UpdateFlags::UNKNOWN != 0 {
+            buffer.extend(0u32.to_le_bytes());
+        }
+
+        if flags & UpdateFlags::LOW_GUID != 0 {
+            buffer.extend(movement.low_guid.ok_or_else(|| missing("low_guid:u32"))?.to_le_bytes());
+        }
+
+        if flags & UpdateFlags::HAS_TARGET != 0 {
+            PackedGuid(movement.target.ok_or_else(|| missing("target:PackedGuid"))?).write_into(buffer)?;
+        }
+
+        if flags & UpdateFlags::TRANSPORT != 0 {
+            buffer.extend(movement.transport_time.ok_or_else(|| missing("transport_time:u32"))?.to_le_bytes());
+        }
+
+        if flags & UpdateFlags::VEHICLE != 0 {
+            let vehicle = movement.vehicle.ok_or_else(|| missing("vehicle:Vehicle"))?;
+            buffer.extend(vehicle.id.to_le_bytes());
+            buffer.extend(vehicle.orientation.to_le_bytes());
+        }
+
+        if flags & UpdateFlags::ROTATION != 0 {
+            buffer.extend(movement.rotation.ok_or_else(|| missing("rotation:u64"))?.to_le_bytes());
+        }
+
+        Ok(())
+    }
+
+    fn read_movement<R: BufRead>(reader: &mut R) -> Result<MovementBlock, FieldError> {
+        let update_flags = reader.read_u16::<LittleEndian>().map_err(Self::read_error("update_flags:u16"))?;
+        let mut movement = MovementBlock { update_flags, ..MovementBlock::default() };
+
+        if update_flags & UpdateFlags::LIVING != 0 {
+            let info = MovementInfo::read_from(&mut *reader)?;
+            if info.flags & MovementFlags::SPLINE_ENABLED != 0 {
+                return Err(Self::invalid_data("movement_flags:u32", "spline movement is not supported"));
+            }
+
+            movement.living = Some(LivingMovement {
+                movement: info,
+                speeds: Speeds::read_from(&mut *reader)?,
+            });
+        } else if update_flags & UpdateFlags::POSITION != 0 {
+            return Err(Self::invalid_data("update_flags:u16", "transport position is not supported"));
+        } else if update_flags & UpdateFlags::STATIONARY_POSITION != 0 {
+            movement.position = Some(Position::read_from(&mut *reader)?);
+        }
+
+        if update_flags & UpdateFlags::UNKNOWN != 0 {
+            reader.read_u32::<LittleEndian>().map_err(Self::read_error("unknown:u32"))?;
+        }
+
+        if update_flags & UpdateFlags::LOW_GUID != 0 {
+            movement.low_guid = Some(reader.read_u32::<LittleEndian>().map_err(Self::read_error("low_guid:u32"))?);
+        }
+
+        if update_flags & UpdateFlags::HAS_TARGET != 0 {
+            movement.target = Some(PackedGuid::read_from(&mut *reader)?.0);
+        }
+
+        if update_flags & UpdateFlags::TRANSPORT != 0 {
+            movement.transport_time = Some(reader.read_u32::<LittleEndian>().map_err(Self::read_error("transport_time:u32"))?);
+        }
+
+        if update_flags & UpdateFlags::VEHICLE != 0 {
+            movement.vehicle = Some(Vehicle {
+                id: reader.read_u32::<LittleEndian>().map_err(Self::read_error("vehicle_id:u32"))?,
+                orientation: reader.read_f32::<LittleEndian>().map_err(Self::read_error("vehicle_orientation:f32"))?,
+            });
+        }
+
+        if update_flags & UpdateFlags::ROTATION != 0 {
+            movement.rotation = Some(reader.read_u64::<LittleEndian>().map_err(Self::read_error("rotation:u64"))?);
+        }
+
+        Ok(movement)
+    }
+}
+
+impl<'de> Deserialize<'de> for UpdateBlocks {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: Deserializer<'de> {
+        Ok(Self(Vec::<UpdateBlock>::deserialize(deserializer)?))
+    }
+}
+
+impl Serialize for UpdateBlocks {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: Serializer {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use tentacli::traits::BinaryConverter;
+
+    use crate::primary::types::fields::movement::{JumpInfo, MovementFlags, MovementInfo, Position, Speeds};
+    use crate::primary::types::fields::update_blocks::{
+        CreateBlock, LivingMovement, MovementBlock, UpdateBlock, UpdateBlocks, UpdateMask, UpdateValues,
+    };
+
+    fn blocks() -> UpdateBlocks {
+        UpdateBlocks(vec![
+            UpdateBlock::SpawnObject(CreateBlock {
+                guid: 1,
+                object_type: 4,
+                movement: MovementBlock {
+                    update_flags: 0x0021,
+                    living: Some(LivingMovement {
+                        movement: MovementInfo {
+                            flags: MovementFlags::FALLING,
+                            time: 1000,
+                            position: Position { x: -618.518, y: -4251.67, z: 38.718, orientation: 0.0 },
+                            jump: Some(JumpInfo::default()),
+                            ..MovementInfo::default()
+                        },
+                        speeds: Speeds::default(),
+                    }),
+                    ..MovementBlock::default()
+                },
+                values: UpdateValues { mask_blocks: 42, fields: BTreeMap::from([(0, 1), (2, 0x19), (1279, 80)]) },
+            }),
+            UpdateBlock::CreateObject(CreateBlock {
+                guid: 0xF1300000000000FF,
+                object_type: 5,
+                movement: MovementBlock {
+                    update_flags: 0x0250,
+                    position: Some(Position { x: 1.0, y: 2.0, z: 3.0, orientation: 4.0 }),
+                    low_guid: Some(0xFF),
+                    rotation: Some(12345),
+                    ..MovementBlock::default()
+                },
+                values: UpdateValues { mask_blocks: 1, fields: BTreeMap::from([(0, 0xFF)]) },
+            }),
+            UpdateBlock::Values { guid: 1, values: UpdateValues { mask_blocks: 5, fields: BTreeMap::from([(30, 2)]) } },
+            UpdateBlock::OutOfRange { guids: vec![2, 3] },
+        ])
+    }
+
+    #[test]
+    fn test_binary_round_trip() {
+        let mut buffer = Vec::new();
+        blocks().write_into(&mut buffer).unwrap();
+
+        assert_eq!(buffer[..4], 4u32.to_le_bytes());
+        // spawn type, packed guid, player type and update flags
+        assert_eq!(buffer[4..10], [3, 1, 1, 4, 0x21, 0]);
+        assert_eq!(UpdateBlocks::read_from(buffer.as_slice()).unwrap(), blocks());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let json = serde_json::to_string(&blocks()).unwrap();
+
+        assert_eq!(serde_json::from_str::<UpdateBlocks>(&json).unwrap(), blocks());
+    }
+
+    #[test]
+    fn test_values_outside_of_mask() {
+        let values = UpdateValues { mask_blocks: 1, fields: BTreeMap::from([(32, 1)]) };
+        let mut blocks = UpdateBlocks(vec![UpdateBlock::Values { guid: 1, values }]);
+
+        assert!(blocks.write_into(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_missing_flag_data() {
+        let movement = MovementBlock { update_flags: 0x0020, ..MovementBlock::default() };
+        let create = CreateBlock { movement, ..CreateBlock::default() };
+
+        assert!(UpdateBlocks(vec![UpdateBlock::CreateObject(create)]).write_into(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_spline_movement() {
+        let mut buffer = Vec::new();
+        blocks().write_into(&mut buffer).unwrap();
+        // movement flags of the spawned player follow its update flags
+        buffer[13] |= (MovementFlags::SPLINE_ENABLED >> 24) as u8;
+        assert!(UpdateBlocks::read_from(buffer.as_slice()).is_err());
+
+        let mut blocks = blocks();
+        if let UpdateBlock::SpawnObject(create) = &mut blocks.0[0] {
+            create.movement.living.as_mut().unwrap().movement.flags |= MovementFlags::SPLINE_ENABLED;
+        }
+        assert!(blocks.write_into(&mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_update_mask() {
+        let mut mask = UpdateMask::new(40);
+        mask.set(3);
+        mask.set(33);
+
+        assert_eq!(mask.blocks(), [0x08, 0x02]);
+        assert_eq!(mask.indexes().collect::<Vec<u16>>(), vec![3, 33]);
+
+        mask.clear();
+        assert!(mask.is_empty());
+    }
+}
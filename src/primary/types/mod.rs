@@ -1,23 +1,33 @@
 pub mod fields;
+mod account;
+mod character;
+mod session;
 
 use std::sync::{Arc, Mutex as SyncMutex};
 use anyhow::{Result as AnyResult};
-use crate::primary::crypto::srp::Srp;
 
 use crate::primary::traits::packet_handler::PacketHandler;
+use crate::primary::traits::server::RunOptions;
+
+pub use account::Account;
+pub use character::Character;
+pub use session::Session;
 
 #[derive(Debug)]
 pub struct HandlerInput {
     pub data: Vec<u8>,
     pub opcode: u16,
-    pub srp: Arc<SyncMutex<Srp>>,
+    pub session: Arc<SyncMutex<Session>>,
+    pub options: Arc<RunOptions>,
+    // filled by the handler which parsed the packet, used in logs and captures
+    pub json_details: Option<String>,
 }
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub enum HandlerOutput {
     // data transfer
-    Data(Vec<u8>),
+    Data(OutgoingPacket),
     SessionKey(Vec<u8>),
 }
 
@@ -33,6 +43,7 @@ pub struct IncomingPacket {
     pub body: Vec<u8>,
 }
 
+// built with unpack() of the packet struct, data is the packet with header
 #[derive(Default, Debug, Clone)]
 pub struct OutgoingPacket {
     pub opcode: u32,
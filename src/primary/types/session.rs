@@ -0,0 +1,52 @@
+use anyhow::{anyhow, Result as AnyResult};
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::primary::crypto::header_crypt::HeaderCrypt;
+use crate::primary::crypto::srp::Srp;
+use crate::primary::server::{OnlineGuard, SessionKey};
+use crate::primary::types::OutgoingPacket;
+
+pub type OutboundSender = UnboundedSender<OutgoingPacket>;
+
+// state that belongs to a single client connection
+#[derive(Debug, Default)]
+pub struct Session {
+    // unique across all servers of the process
+    pub connection_id: u64,
+    // local port of the server which accepted the connection
+    pub port: u16,
+    // packets sent here are written to the client socket in the order they were queued
+    pub outbound: Option<OutboundSender>,
+    // created on LOGIN_CHALLENGE and consumed on LOGIN_PROOF
+    pub srp: Option<Srp>,
+    // account named in LOGIN_CHALLENGE or RECONNECT_CHALLENGE, not yet proven
+    pub challenged_account: Option<String>,
+    // set only after the client proved the account on LOGIN_PROOF or RECONNECT_PROOF
+    pub account: Option<String>,
+    // random data sent on RECONNECT_CHALLENGE, client should prove it with stored session key
+    pub reconnect_proof: Option<[u8; 16]>,
+    // realm served by the world server which accepted the connection
+    pub realm_id: u8,
+    // sent to the client in SMSG_AUTH_CHALLENGE, used to verify CMSG_AUTH_SESSION digest
+    pub server_seed: u32,
+    pub session_key: Option<SessionKey>,
+    // world packet headers are encrypted after successful CMSG_AUTH_SESSION
+    pub header_crypt: Option<HeaderCrypt>,
+    // keeps account counted as online while the world connection is alive
+    pub online: Option<OnlineGuard>,
+    // guid of the character which entered the world with CMSG_PLAYER_LOGIN
+    pub character: Option<u64>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn send(&self, packet: OutgoingPacket) -> AnyResult<()> {
+        self.outbound.as_ref()
+            .ok_or_else(|| anyhow!("Connection is closed"))?
+            .send(packet)
+            .map_err(|_| anyhow!("Connection is closed"))
+    }
+}
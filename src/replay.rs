@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use std::env;
+use std::process::ExitCode;
+use std::time::Duration;
+use anyhow::{anyhow, bail, Result as AnyResult};
+use tine::primary::capture::CaptureReader;
+use tine::primary::config::parse_args;
+use tine::primary::replay::{Replay, ReplayOptions};
+
+const USAGE: &str = "Usage: tine-replay --capture <PATH> [OPTIONS]
+
+Replays client packets recorded with tine --capture against a running tine
+and compares server responses with the recorded ones.
+
+Options:
+  --capture <PATH>                file recorded with tine --capture
+  --host <HOST>                   address of the running tine (default: 127.0.0.1)
+  --account <NAME:PASSWORD>       password to sign the recorded logins of the account, can be repeated
+  --timeout <MILLISECONDS>        time to wait for every response (default: 3000)
+  --help                          print this message
+
+Without the account password recorded proofs are sent as is and the server rejects them.
+Exit code is 1 when some responses differ from the capture.";
+
+#[tokio::main]
+async fn main() -> AnyResult<ExitCode> {
+    let args = parse_args(env::args().skip(1), USAGE)?;
+    if args.iter().any(|(flag, _)| flag == "help") {
+        println!("{}", USAGE);
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut capture_path = None;
+    let mut options = ReplayOptions {
+        host: "127.0.0.1".to_string(),
+        passwords: HashMap::new(),
+        timeout: Duration::from_millis(3000),
+    };
+
+    for (flag, value) in &args {
+        match flag.as_str() {
+            "capture" => capture_path = Some(value.clone()),
+            "host" => options.host = value.clone(),
+            "account" => {
+                let (name, password) = value.split_once(':')
+                    .ok_or_else(|| anyhow!("Account should be passed as NAME:PASSWORD"))?;
+                options.passwords.insert(name.to_uppercase(), password.to_string());
+            },
+            "timeout" => options.timeout = Duration::from_millis(value.parse()?),
+            _ => bail!("Unknown option: --{}\n\n{}", flag, USAGE),
+        }
+    }
+
+    let capture_path = capture_path.ok_or_else(|| anyhow!("Missing --capture\n\n{}", USAGE))?;
+    let records = CaptureReader::open(&capture_path)?.collect::<AnyResult<Vec<_>>>()?;
+
+    let report = Replay::new(options).run(records).await?;
+    print!("{}", report);
+
+    match report.has_differences() {
+        true => Ok(ExitCode::FAILURE),
+        false => Ok(ExitCode::SUCCESS),
+    }
+}